@@ -0,0 +1,721 @@
+use crate::dev::node::Node;
+use crate::dev::orientation::{AddEdge, Directed, Undirected};
+use crate::dev::transform::{transformers, Collect, Map};
+use crate::dev::{
+    AddVertex, Edges, GetEdge, GetEdgeTo, GetVertex, Merge, Neighbours, RemoveEdge, RemoveVertex,
+    Vertices,
+};
+use std::collections::HashMap;
+use std::slice;
+
+///Key of a vertex, being the position of its slot along with the generation of the slot, such that the key of a removed
+///vertex is never mistaken for the key of a vertex reusing its slot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct VertexIndex {
+    slot: usize,
+    generation: usize,
+}
+
+impl VertexIndex {
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+///Key of an edge, being the position of its slot along with the generation of the slot, such that the key of a removed
+///edge is never mistaken for the key of an edge reusing its slot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct EdgeIndex {
+    slot: usize,
+    generation: usize,
+}
+
+impl EdgeIndex {
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+trait Key: Copy + Eq {
+    fn new(slot: usize, generation: usize) -> Self;
+    fn parts(&self) -> (usize, usize);
+}
+
+impl Key for VertexIndex {
+    fn new(slot: usize, generation: usize) -> Self {
+        Self { slot, generation }
+    }
+
+    fn parts(&self) -> (usize, usize) {
+        (self.slot, self.generation)
+    }
+}
+
+impl Key for EdgeIndex {
+    fn new(slot: usize, generation: usize) -> Self {
+        Self { slot, generation }
+    }
+
+    fn parts(&self) -> (usize, usize) {
+        (self.slot, self.generation)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Slot<K, Value> {
+    key: K,
+    value: Option<Value>,
+}
+
+///Values stored in slots, where the slots of removed values are reused by the next insertions under a new generation.
+#[derive(Clone, Debug)]
+struct Arena<K, Value> {
+    slots: Vec<Slot<K, Value>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<K, Value> Default for Arena<K, Value> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+///Arenas are equal when the same keys hold equal values, no matter which slots are free.
+impl<K, Value> PartialEq for Arena<K, Value>
+where
+    K: PartialEq,
+    Value: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let occupied = |slot: &&Slot<K, Value>| slot.value.is_some();
+        self.len == other.len
+            && self
+                .slots
+                .iter()
+                .filter(occupied)
+                .eq(other.slots.iter().filter(occupied))
+    }
+}
+
+impl<K, Value> Eq for Arena<K, Value>
+where
+    K: Eq,
+    Value: Eq,
+{
+}
+
+impl<K, Value> Arena<K, Value>
+where
+    K: Key,
+{
+    fn insert(&mut self, value: Value) -> K {
+        self.len += 1;
+        match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].value = Some(value);
+                self.slots[slot].key
+            }
+            None => {
+                let key = K::new(self.slots.len(), 0);
+                self.slots.push(Slot {
+                    key,
+                    value: Some(value),
+                });
+                key
+            }
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<&Value> {
+        let slot = self.slots.get(key.parts().0)?;
+        if slot.key == *key {
+            slot.value.as_ref()
+        } else {
+            None
+        }
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut Value> {
+        let slot = self.slots.get_mut(key.parts().0)?;
+        if slot.key == *key {
+            slot.value.as_mut()
+        } else {
+            None
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<Value> {
+        let (index, generation) = key.parts();
+        let slot = self.slots.get_mut(index)?;
+        if slot.key != *key {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.key = K::new(index, generation + 1);
+        self.free.push(index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn map<Value2, Func>(self, mut func: Func) -> Arena<K, Value2>
+    where
+        Func: FnMut(Value) -> Value2,
+    {
+        Arena {
+            slots: self
+                .slots
+                .into_iter()
+                .map(|x| Slot {
+                    key: x.key,
+                    value: x.value.map(&mut func),
+                })
+                .collect(),
+            free: self.free,
+            len: self.len,
+        }
+    }
+}
+
+///Iterator over the keys of the occupied slots.
+pub struct Keys<'a, K, Value> {
+    slots: slice::Iter<'a, Slot<K, Value>>,
+}
+
+impl<'a, K, Value> Iterator for Keys<'a, K, Value> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find(|x| x.value.is_some()).map(|x| &x.key)
+    }
+}
+
+type VertexNode<Vertex> = Node<Vertex, Vec<EdgeIndex>, Vec<EdgeIndex>>;
+
+///A graph storing its vertices and edges in slots of vectors, with the keys generated on insertion.
+///Every vertex holds its outgoing edges in `to` and its incoming edges in `from`, while undirected edges are outgoing
+///from both of their ends. Lookups index the vectors directly, without hashing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Indexed<Vertex, Edge> {
+    vertices: Arena<VertexIndex, VertexNode<Vertex>>,
+    edges: Arena<EdgeIndex, Node<Edge, VertexIndex, VertexIndex>>,
+}
+
+impl<Vertex, Edge> Default for Indexed<Vertex, Edge> {
+    fn default() -> Self {
+        Self {
+            vertices: Arena::default(),
+            edges: Arena::default(),
+        }
+    }
+}
+
+impl<Vertex, Edge> Indexed<Vertex, Edge> {
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len
+    }
+
+    ///Moves every vertex and edge of the other graph into this one, under new keys.
+    ///Returns the new keys of the vertices and of the edges, by their keys in the other graph.
+    pub fn merge_with_keys(
+        &mut self,
+        other: Self,
+    ) -> (
+        HashMap<VertexIndex, VertexIndex>,
+        HashMap<EdgeIndex, EdgeIndex>,
+    ) {
+        let mut vertices = HashMap::new();
+        for slot in other.vertices.slots {
+            if let Some(node) = slot.value {
+                vertices.insert(slot.key, self.vertices.insert(node));
+            }
+        }
+
+        let mut edges = HashMap::new();
+        for slot in other.edges.slots {
+            if let Some(node) = slot.value {
+                let node = Node {
+                    data: node.data,
+                    from: vertices[&node.from],
+                    to: vertices[&node.to],
+                };
+                edges.insert(slot.key, self.edges.insert(node));
+            }
+        }
+
+        for key in vertices.values() {
+            let node = self.vertices.get_mut(key).unwrap();
+            for edge in node.from.iter_mut().chain(node.to.iter_mut()) {
+                *edge = edges[edge];
+            }
+        }
+        (vertices, edges)
+    }
+}
+
+impl<Vertex, Edge> AddVertex<Vertex> for Indexed<Vertex, Edge> {
+    type Key = VertexIndex;
+
+    fn add_vertex(&mut self, data: Vertex) -> Result<Self::Key, Vertex> {
+        Ok(self.vertices.insert(Node {
+            data,
+            from: Vec::new(),
+            to: Vec::new(),
+        }))
+    }
+}
+
+impl<Vertex, Edge> AddEdge<Directed, VertexIndex, Edge> for Indexed<Vertex, Edge> {
+    type EdgeKey = EdgeIndex;
+
+    fn add_edge(
+        &mut self,
+        from: &VertexIndex,
+        to: &VertexIndex,
+        data: Edge,
+    ) -> Result<Self::EdgeKey, Edge> {
+        if self.vertices.get(from).is_none() || self.vertices.get(to).is_none() {
+            return Err(data);
+        }
+        let key = self.edges.insert(Node {
+            data,
+            from: *from,
+            to: *to,
+        });
+        self.vertices.get_mut(from).unwrap().to.push(key);
+        self.vertices.get_mut(to).unwrap().from.push(key);
+        Ok(key)
+    }
+}
+
+impl<Vertex, Edge> AddEdge<Undirected, VertexIndex, Edge> for Indexed<Vertex, Edge> {
+    type EdgeKey = EdgeIndex;
+
+    fn add_edge(
+        &mut self,
+        from: &VertexIndex,
+        to: &VertexIndex,
+        data: Edge,
+    ) -> Result<Self::EdgeKey, Edge> {
+        if self.vertices.get(from).is_none() || self.vertices.get(to).is_none() {
+            return Err(data);
+        }
+        let key = self.edges.insert(Node {
+            data,
+            from: *from,
+            to: *to,
+        });
+        self.vertices.get_mut(from).unwrap().to.push(key);
+        if from != to {
+            self.vertices.get_mut(to).unwrap().to.push(key);
+        }
+        Ok(key)
+    }
+}
+
+pub type RemovedVertex<Vertex, Edge> = (
+    VertexIndex,
+    Node<
+        Vertex,
+        Vec<(EdgeIndex, Node<Edge, VertexIndex, VertexIndex>)>,
+        Vec<(EdgeIndex, Node<Edge, VertexIndex, VertexIndex>)>,
+    >,
+);
+
+impl<Vertex, Edge> RemoveVertex<VertexIndex> for Indexed<Vertex, Edge> {
+    type Output = RemovedVertex<Vertex, Edge>;
+
+    fn remove_vertex(&mut self, key: &VertexIndex) -> Option<Self::Output> {
+        let node = self.vertices.remove(key)?;
+
+        let new_node = Node {
+            data: node.data,
+            from: node
+                .from
+                .into_iter()
+                .flat_map(|key| self.remove_edge(&key))
+                .collect(),
+            to: node
+                .to
+                .into_iter()
+                .flat_map(|key| self.remove_edge(&key))
+                .collect(),
+        };
+
+        Some((*key, new_node))
+    }
+}
+
+impl<Vertex, Edge> RemoveEdge<EdgeIndex> for Indexed<Vertex, Edge> {
+    type Output = (EdgeIndex, Node<Edge, VertexIndex, VertexIndex>);
+
+    fn remove_edge(&mut self, key: &EdgeIndex) -> Option<Self::Output> {
+        let node = self.edges.remove(key)?;
+        for vertex in &[node.from, node.to] {
+            if let Some(vertex) = self.vertices.get_mut(vertex) {
+                vertex.from.retain(|x| x != key);
+                vertex.to.retain(|x| x != key);
+            }
+        }
+        Some((*key, node))
+    }
+}
+
+impl<Vertex, Edge> GetVertex<VertexIndex> for Indexed<Vertex, Edge> {
+    type Output = Vertex;
+
+    fn get_vertex(&self, key: &VertexIndex) -> Option<&Self::Output> {
+        self.vertices.get(key).map(|node| &node.data)
+    }
+}
+
+impl<Vertex, Edge> GetEdge<EdgeIndex> for Indexed<Vertex, Edge> {
+    type Output = Edge;
+
+    fn get_edge(&self, key: &EdgeIndex) -> Option<&Self::Output> {
+        self.edges.get(key).map(|node| &node.data)
+    }
+}
+
+impl<'a, Vertex, Edge> GetEdgeTo<'a, EdgeIndex> for Indexed<Vertex, Edge> {
+    type Output = &'a VertexIndex;
+
+    fn get_edge_to(&'a self, key: &EdgeIndex) -> Option<Self::Output> {
+        self.edges.get(key).map(|node| &node.to)
+    }
+}
+
+impl<'a, Vertex, Edge> Neighbours<'a, Directed, VertexIndex> for Indexed<Vertex, Edge> {
+    type Edge = &'a EdgeIndex;
+    type IntoIter = Vec<(Self::Edge, &'a VertexIndex)>;
+
+    fn neighbours(&'a self, vertex: &VertexIndex) -> Option<Self::IntoIter> {
+        self.vertices
+            .get(vertex)?
+            .to
+            .iter()
+            .flat_map(|key| Some((key, self.edges.get(key)?.other(vertex))))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl<'a, Vertex, Edge> Neighbours<'a, Undirected, VertexIndex> for Indexed<Vertex, Edge> {
+    type Edge = &'a EdgeIndex;
+    type IntoIter = Vec<(Self::Edge, &'a VertexIndex)>;
+
+    fn neighbours(&'a self, vertex: &VertexIndex) -> Option<Self::IntoIter> {
+        let node = self.vertices.get(vertex)?;
+        let to = node
+            .to
+            .iter()
+            .flat_map(|key| Some((key, self.edges.get(key)?.other(vertex))));
+
+        //Directed self loops are both outgoing and incoming, yet only one edge.
+        let from = node
+            .from
+            .iter()
+            .flat_map(|key| Some((key, self.edges.get(key)?.other(vertex))))
+            .filter(|x| x.1 != vertex);
+
+        to.chain(from).collect::<Vec<_>>().into()
+    }
+}
+
+impl<'a, Vertex, Edge> Vertices<'a> for Indexed<Vertex, Edge>
+where
+    Vertex: 'a,
+    Edge: 'a,
+{
+    type Item = VertexIndex;
+    type Output = Keys<'a, VertexIndex, VertexNode<Vertex>>;
+
+    fn vertices(&'a self) -> Self::Output {
+        Keys {
+            slots: self.vertices.slots.iter(),
+        }
+    }
+}
+
+impl<'a, Vertex, Edge> Edges<'a> for Indexed<Vertex, Edge>
+where
+    Vertex: 'a,
+    Edge: 'a,
+{
+    type Item = EdgeIndex;
+    type Output = Keys<'a, EdgeIndex, Node<Edge, VertexIndex, VertexIndex>>;
+
+    fn edges(&'a self) -> Self::Output {
+        Keys {
+            slots: self.edges.slots.iter(),
+        }
+    }
+}
+
+///Moves every vertex and edge of the other graph into this one, under new keys. As keys are generated, this never conflicts.
+impl<Vertex, Edge> Merge for Indexed<Vertex, Edge> {
+    type Output = Self;
+
+    fn merge(mut self, other: Self) -> Result<Self::Output, (Self, Self)> {
+        self.merge_with_keys(other);
+        Ok(self)
+    }
+}
+
+///Transforms the data of the vertices and edges, keeping every key. The keys themselves are generated, and thus cannot be mapped.
+pub struct IndexedTransformer<Vertex, Edge> {
+    graph: Indexed<Vertex, Edge>,
+}
+
+impl<Vertex, Edge> Collect for IndexedTransformer<Vertex, Edge> {
+    type Output = Indexed<Vertex, Edge>;
+
+    fn collect(self) -> Option<Self::Output> {
+        Some(self.graph)
+    }
+}
+
+impl<Func, Vertex, Vertex2, Edge> Map<transformers::Vertex, VertexIndex, VertexIndex, Func>
+    for Indexed<Vertex, Edge>
+where
+    Func: Fn(Vertex) -> Vertex2,
+{
+    type Mapper = IndexedTransformer<Vertex2, Edge>;
+
+    fn map(self, func: Func) -> Self::Mapper {
+        Map::<transformers::Vertex, _, _, _>::map(IndexedTransformer { graph: self }, func)
+    }
+}
+
+impl<Func, Vertex, Edge, Edge2> Map<transformers::Edge, VertexIndex, VertexIndex, Func>
+    for Indexed<Vertex, Edge>
+where
+    Func: Fn(Edge) -> Edge2,
+{
+    type Mapper = IndexedTransformer<Vertex, Edge2>;
+
+    fn map(self, func: Func) -> Self::Mapper {
+        Map::<transformers::Edge, _, _, _>::map(IndexedTransformer { graph: self }, func)
+    }
+}
+
+impl<Func, Vertex, Vertex2, Edge> Map<transformers::Vertex, VertexIndex, VertexIndex, Func>
+    for IndexedTransformer<Vertex, Edge>
+where
+    Func: Fn(Vertex) -> Vertex2,
+{
+    type Mapper = IndexedTransformer<Vertex2, Edge>;
+
+    fn map(self, func: Func) -> Self::Mapper {
+        let vertices = self.graph.vertices.map(|node| Node {
+            data: func(node.data),
+            from: node.from,
+            to: node.to,
+        });
+        IndexedTransformer {
+            graph: Indexed {
+                vertices,
+                edges: self.graph.edges,
+            },
+        }
+    }
+}
+
+impl<Func, Vertex, Edge, Edge2> Map<transformers::Edge, VertexIndex, VertexIndex, Func>
+    for IndexedTransformer<Vertex, Edge>
+where
+    Func: Fn(Edge) -> Edge2,
+{
+    type Mapper = IndexedTransformer<Vertex, Edge2>;
+
+    fn map(self, func: Func) -> Self::Mapper {
+        let edges = self.graph.edges.map(|node| Node {
+            data: func(node.data),
+            from: node.from,
+            to: node.to,
+        });
+        IndexedTransformer {
+            graph: Indexed {
+                vertices: self.graph.vertices,
+                edges,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extended::cover::Cover;
+    use crate::extended::feedback::FeedbackArcSet;
+    use crate::wrapper::oriented::Orient;
+
+    #[test]
+    fn generations() {
+        let mut graph: Indexed<&str, ()> = Indexed::default();
+        let a = graph.add_vertex("a").unwrap();
+        let b = graph.add_vertex("b").unwrap();
+        let edge = AddEdge::<Directed, _, _>::add_edge(&mut graph, &a, &b, ()).unwrap();
+        assert_eq!(graph.get_edge_to(&edge), Some(&b));
+
+        let (removed, node) = graph.remove_vertex(&a).unwrap();
+        assert_eq!(removed, a);
+        assert_eq!(node.to.len(), 1);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.get_edge(&edge), None);
+        assert!(graph.remove_vertex(&a).is_none());
+
+        //The slot of a is reused, but its old key does not reach the new vertex.
+        let c = graph.add_vertex("c").unwrap();
+        assert_eq!(c.slot(), a.slot());
+        assert_eq!(c.generation(), a.generation() + 1);
+        assert_eq!(graph.get_vertex(&a), None);
+        assert_eq!(graph.get_vertex(&c), Some(&"c"));
+        assert_eq!(
+            AddEdge::<Directed, _, _>::add_edge(&mut graph, &a, &b, ()),
+            Err(())
+        );
+        assert_eq!(graph.vertices().collect::<Vec<_>>(), vec![&c, &b]);
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(
+            Neighbours::<Directed, _>::neighbours(&graph, &b),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let mut graph: Indexed<(), ()> = Indexed::default();
+        let keys: Vec<_> = (0..3).map(|_| graph.add_vertex(()).unwrap()).collect();
+        let directed =
+            AddEdge::<Directed, _, _>::add_edge(&mut graph, &keys[0], &keys[1], ()).unwrap();
+        let undirected =
+            AddEdge::<Undirected, _, _>::add_edge(&mut graph, &keys[1], &keys[2], ()).unwrap();
+        let looped =
+            AddEdge::<Directed, _, _>::add_edge(&mut graph, &keys[2], &keys[2], ()).unwrap();
+
+        let directed_neighbours = |x| Neighbours::<Directed, _>::neighbours(&graph, x).unwrap();
+        let undirected_neighbours = |x| Neighbours::<Undirected, _>::neighbours(&graph, x).unwrap();
+        assert_eq!(directed_neighbours(&keys[0]), vec![(&directed, &keys[1])]);
+        assert_eq!(directed_neighbours(&keys[1]), vec![(&undirected, &keys[2])]);
+        assert_eq!(
+            directed_neighbours(&keys[2]),
+            vec![(&undirected, &keys[1]), (&looped, &keys[2])]
+        );
+        assert_eq!(
+            undirected_neighbours(&keys[1]),
+            vec![(&undirected, &keys[2]), (&directed, &keys[0])]
+        );
+        assert_eq!(undirected_neighbours(&keys[2]).len(), 2);
+
+        graph.remove_edge(&undirected).unwrap();
+        assert!(Neighbours::<Directed, _>::neighbours(&graph, &keys[1])
+            .unwrap()
+            .is_empty());
+        assert_eq!(graph.edges().count(), 2);
+    }
+
+    #[test]
+    fn equality() {
+        let mut graph: Indexed<&str, ()> = Indexed::default();
+        let a = graph.add_vertex("a").unwrap();
+        let b = graph.add_vertex("b").unwrap();
+        graph.remove_vertex(&b).unwrap();
+
+        //Only the occupied slots are compared, not the freed ones.
+        let mut other: Indexed<&str, ()> = Indexed::default();
+        assert_eq!(other.add_vertex("a"), Ok(a));
+        assert_eq!(graph, other);
+
+        other.remove_vertex(&a).unwrap();
+        other.add_vertex("a").unwrap();
+        assert_ne!(graph, other);
+    }
+
+    #[test]
+    fn merge() {
+        let mut graph: Indexed<&str, usize> = Indexed::default();
+        let a = graph.add_vertex("a").unwrap();
+        let b = graph.add_vertex("b").unwrap();
+        AddEdge::<Directed, _, _>::add_edge(&mut graph, &a, &b, 0).unwrap();
+        graph.remove_vertex(&b).unwrap();
+
+        let mut other: Indexed<&str, usize> = Indexed::default();
+        let c = other.add_vertex("c").unwrap();
+        let d = other.add_vertex("d").unwrap();
+        let first = AddEdge::<Directed, _, _>::add_edge(&mut other, &c, &d, 1).unwrap();
+        let looped = AddEdge::<Undirected, _, _>::add_edge(&mut other, &d, &d, 2).unwrap();
+
+        let (vertices, edges) = graph.clone().merge_with_keys(other.clone());
+        assert_eq!(vertices.len(), 2);
+        assert_eq!(edges.len(), 2);
+
+        let merged = graph.merge(other).unwrap();
+        assert_eq!(merged.vertex_count(), 3);
+        assert_eq!(merged.edge_count(), 2);
+        let (c, d) = (vertices[&c], vertices[&d]);
+        assert_eq!(merged.get_vertex(&c), Some(&"c"));
+        //The slot freed by b is reused by c.
+        assert_eq!(c.slot(), b.slot());
+        assert_eq!(
+            Neighbours::<Directed, _>::neighbours(&merged, &c),
+            Some(vec![(&edges[&first], &d)])
+        );
+        assert_eq!(merged.get_edge(&edges[&first]), Some(&1));
+        assert_eq!(
+            Neighbours::<Directed, _>::neighbours(&merged, &d),
+            Some(vec![(&edges[&looped], &d)])
+        );
+    }
+
+    #[test]
+    fn map() {
+        let mut graph: Indexed<usize, usize> = Indexed::default();
+        let a = graph.add_vertex(1).unwrap();
+        let b = graph.add_vertex(2).unwrap();
+        let edge = AddEdge::<Directed, _, _>::add_edge(&mut graph, &a, &b, 3).unwrap();
+
+        let mapper = Map::<transformers::Vertex, _, _, _>::map(graph, |x: usize| x.to_string());
+        let mapper = Map::<transformers::Edge, _, _, _>::map(mapper, |x: usize| x as f64 / 2.0);
+        let mapped = mapper.collect().unwrap();
+        assert_eq!(mapped.get_vertex(&b), Some(&"2".to_string()));
+        assert_eq!(mapped.get_edge(&edge), Some(&1.5));
+    }
+
+    #[test]
+    fn algorithms() {
+        //A five cycle, both as undirected graph and as directed cycle.
+        let mut undirected = Indexed::<(), ()>::default().orient(Undirected);
+        let keys: Vec<_> = (0..5).map(|_| undirected.add_vertex(()).unwrap()).collect();
+        for i in 0..5 {
+            undirected
+                .add_edge(&keys[i], &keys[(i + 1) % 5], ())
+                .unwrap();
+        }
+        assert_eq!(undirected.vertex_cover().unwrap().len(), 3);
+
+        let mut directed = Indexed::<(), ()>::default().orient(Directed);
+        let keys: Vec<_> = (0..5).map(|_| directed.add_vertex(()).unwrap()).collect();
+        for i in 0..5 {
+            directed.add_edge(&keys[i], &keys[(i + 1) % 5], ()).unwrap();
+        }
+        assert_eq!(directed.minimum_feedback_arc_set().unwrap().len(), 1);
+        assert_eq!(directed.feedback_arc_set().len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod indexed;
 pub mod node;
 pub mod simple;
 pub mod transform;
@@ -75,7 +75,10 @@ where
         to: &Vk,
         (key, data): (Ek, E),
     ) -> Result<Self::EdgeKey, (Ek, E)> {
-        if !self.vertices.contains_key(&from) {
+        if !self.vertices.contains_key(&from)
+            || !self.vertices.contains_key(to)
+            || self.edges.contains_key(&key)
+        {
             return Err((key, data));
         }
 
@@ -545,3 +548,49 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Simple<u32, (), u32, ()> {
+        let mut graph = Simple::default();
+        graph.add_vertex((0, ())).unwrap();
+        graph.add_vertex((1, ())).unwrap();
+        graph
+    }
+
+    #[test]
+    fn missing_vertex() {
+        let mut graph = graph();
+        assert_eq!(
+            AddEdge::<Directed, _, _>::add_edge(&mut graph, &0, &2, (0, ())),
+            Err((0, ()))
+        );
+        assert_eq!(
+            AddEdge::<Undirected, _, _>::add_edge(&mut graph, &2, &0, (0, ())),
+            Err((0, ()))
+        );
+        assert!(graph.edges.is_empty());
+        assert!(graph
+            .vertices
+            .values()
+            .all(|x| x.from.is_empty() && x.to.is_empty()));
+    }
+
+    #[test]
+    fn duplicate_key() {
+        let mut graph = graph();
+        AddEdge::<Directed, _, _>::add_edge(&mut graph, &0, &1, (0, ())).unwrap();
+        assert_eq!(
+            AddEdge::<Directed, _, _>::add_edge(&mut graph, &1, &0, (0, ())),
+            Err((0, ()))
+        );
+        assert_eq!(
+            AddEdge::<Undirected, _, _>::add_edge(&mut graph, &1, &1, (0, ())),
+            Err((0, ()))
+        );
+        assert_eq!(graph.get_edge_to(&0), Some(&1));
+        assert!(graph.vertices[&1].to.is_empty());
+    }
+}
@@ -0,0 +1,560 @@
+use crate::dev::orientation::{AddEdge, Directed};
+use crate::dev::simple::Simple;
+use crate::dev::{AddVertex, GetEdgeTo, GetVertex, Neighbours, Vertices};
+use crate::wrapper::path::Path;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+///The transitions of an automaton, with every edge keyed by its symbol and hashed along with the state it leaves,
+///such that no state has two transitions on the same symbol.
+pub type Transitions<State, Symbol> = Path<Simple<State, (), (State, Symbol), ()>>;
+
+///Deterministic finite automaton, where the states are vertices and the transitions are edges keyed by their input symbols.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dfa<State, Symbol>
+where
+    State: Eq + Hash,
+    Symbol: Eq + Hash,
+{
+    graph: Transitions<State, Symbol>,
+    start: State,
+    accepting: HashSet<State>,
+    alphabet: HashSet<Symbol>,
+}
+
+impl<State, Symbol> Dfa<State, Symbol>
+where
+    State: Eq + Hash + Clone,
+    Symbol: Eq + Hash + Clone,
+{
+    ///Creates an automaton with only the start state, rejecting everything.
+    pub fn new(start: State) -> Self {
+        let mut graph = Transitions::default();
+        let _ = graph.add_vertex((start.clone(), ()));
+        Self {
+            graph,
+            start,
+            accepting: HashSet::new(),
+            alphabet: HashSet::new(),
+        }
+    }
+
+    pub fn graph(&self) -> &Transitions<State, Symbol> {
+        &self.graph
+    }
+
+    pub fn start(&self) -> &State {
+        &self.start
+    }
+
+    pub fn accepting(&self) -> &HashSet<State> {
+        &self.accepting
+    }
+
+    ///The symbols of every transition, along with those added through `extend_alphabet`.
+    pub fn alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    ///Adds symbols to the alphabet, which the automaton needs transitions on to be complete.
+    pub fn extend_alphabet<Symbols>(&mut self, symbols: Symbols)
+    where
+        Symbols: IntoIterator<Item = Symbol>,
+    {
+        self.alphabet.extend(symbols);
+    }
+
+    pub fn states(&self) -> impl Iterator<Item = &State> {
+        self.graph.vertices()
+    }
+
+    ///Adds a state, returning it back if it already exists.
+    pub fn add_state(&mut self, state: State) -> Result<State, State> {
+        self.graph.add_vertex((state, ())).map_err(|x| x.0)
+    }
+
+    ///Adds a transition, returning the symbol back if either state is missing or the state already has a transition on it.
+    pub fn add_transition(
+        &mut self,
+        from: &State,
+        symbol: Symbol,
+        to: &State,
+    ) -> Result<(), Symbol> {
+        AddEdge::<Directed, _, _>::add_edge(&mut self.graph, from, to, (symbol.clone(), ()))
+            .map_err(|x| x.0)?;
+        self.alphabet.insert(symbol);
+        Ok(())
+    }
+
+    ///Marks an existing state as accepting, returning whether it exists.
+    pub fn accept(&mut self, state: State) -> bool {
+        let exists = self.graph.get_vertex(&state).is_some();
+        if exists {
+            self.accepting.insert(state);
+        }
+        exists
+    }
+
+    pub fn is_accepting(&self, state: &State) -> bool {
+        self.accepting.contains(state)
+    }
+
+    ///The state reached from the given state on the given symbol.
+    pub fn transition(&self, state: &State, symbol: Symbol) -> Option<&State> {
+        self.graph.get_edge_to(&(state.clone(), symbol))
+    }
+
+    ///The transitions leaving the given state, as pairs of a symbol and the state reached.
+    pub fn transitions<'a>(
+        &'a self,
+        state: &State,
+    ) -> impl Iterator<Item = (&'a Symbol, &'a State)> {
+        Neighbours::<Directed, _>::neighbours(&self.graph, state)
+            .into_iter()
+            .flatten()
+            .map(|((_, symbol), to)| (symbol, to))
+    }
+
+    ///Starts a step by step execution from the start state.
+    pub fn run(&self) -> Execution<'_, State, Symbol> {
+        Execution {
+            automaton: self,
+            state: Some(&self.start),
+        }
+    }
+
+    ///Whether the input leads from the start state to an accepting state. Inputs falling off a missing transition are rejected.
+    pub fn accepts<Input>(&self, input: Input) -> bool
+    where
+        Input: IntoIterator<Item = Symbol>,
+    {
+        let mut execution = self.run();
+        for symbol in input {
+            if execution.step(symbol).is_none() {
+                return false;
+            }
+        }
+        execution.is_accepting()
+    }
+
+    ///Whether every state has a transition on every symbol of the alphabet.
+    pub fn is_complete(&self) -> bool {
+        self.states()
+            .all(|state| self.transitions(state).count() == self.alphabet.len())
+    }
+
+    ///Completes the automaton by sending every missing transition to a new rejecting dead state, which loops onto itself.
+    ///The dead state is only added if a transition is missing, and is returned back if it already exists.
+    pub fn complete(&mut self, dead: State) -> Result<(), State> {
+        if self.is_complete() {
+            return Ok(());
+        }
+        let dead = self.add_state(dead)?;
+        let states: Vec<_> = self.states().cloned().collect();
+        let alphabet: Vec<_> = self.alphabet.iter().cloned().collect();
+        for state in &states {
+            for symbol in &alphabet {
+                let _ = self.add_transition(state, symbol.clone(), &dead);
+            }
+        }
+        Ok(())
+    }
+
+    ///The equivalent automaton with the fewest states, found using Hopcroft's algorithm, which refines the split between
+    ///accepting and rejecting states until no symbol leads states of the same block into different blocks.
+    ///Unreachable states are left out, as is the state rejecting everything, leaving the transitions to it missing.
+    ///The states are numbered from zero, being the start.
+    pub fn minimize(&self) -> Dfa<usize, Symbol> {
+        let alphabet: Vec<_> = self.alphabet.iter().cloned().collect();
+        let mut keys = vec![&self.start];
+        let mut index = HashMap::new();
+        index.insert(&self.start, 0);
+        let mut i = 0;
+        while i < keys.len() {
+            for (_, to) in self.transitions(keys[i]) {
+                if !index.contains_key(to) {
+                    index.insert(to, keys.len());
+                    keys.push(to);
+                }
+            }
+            i += 1;
+        }
+
+        //A dead state stands in for the missing transitions.
+        let dead = keys.len();
+        let size = dead + 1;
+        let delta: Vec<Vec<_>> = (0..size)
+            .map(|state| {
+                alphabet
+                    .iter()
+                    .map(|symbol| match keys.get(state) {
+                        Some(key) => self
+                            .transition(key, symbol.clone())
+                            .map_or(dead, |x| index[x]),
+                        None => dead,
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut inverse = vec![vec![Vec::new(); size]; alphabet.len()];
+        for (from, row) in delta.iter().enumerate() {
+            for (symbol, &to) in row.iter().enumerate() {
+                inverse[symbol][to].push(from);
+            }
+        }
+        let accepting: Vec<_> = (0..size)
+            .map(|x| x != dead && self.accepting.contains(keys[x]))
+            .collect();
+
+        let (accept, reject): (Vec<_>, Vec<_>) = (0..size).partition(|&x| accepting[x]);
+        let mut blocks = vec![reject];
+        if !accept.is_empty() {
+            blocks.push(accept);
+        }
+        let mut block = vec![0; size];
+        for (i, states) in blocks.iter().enumerate() {
+            for &state in states {
+                block[state] = i;
+            }
+        }
+        let mut work: Vec<_> = (0..blocks.len()).collect();
+        let mut waiting = vec![true; blocks.len()];
+
+        while let Some(splitter) = work.pop() {
+            waiting[splitter] = false;
+            let splitter = blocks[splitter].clone();
+            for inverse in &inverse {
+                let mut touched: HashMap<_, Vec<_>> = HashMap::new();
+                for &to in &splitter {
+                    for &from in &inverse[to] {
+                        touched.entry(block[from]).or_default().push(from);
+                    }
+                }
+                for (old, mut inside) in touched {
+                    inside.sort_unstable();
+                    inside.dedup();
+                    if inside.len() == blocks[old].len() {
+                        continue;
+                    }
+                    let new = blocks.len();
+                    for &state in &inside {
+                        block[state] = new;
+                    }
+                    blocks[old].retain(|&x| block[x] == old);
+                    blocks.push(inside);
+                    waiting.push(false);
+                    let next = if waiting[old] || blocks[new].len() < blocks[old].len() {
+                        new
+                    } else {
+                        old
+                    };
+                    if !waiting[next] {
+                        waiting[next] = true;
+                        work.push(next);
+                    }
+                }
+            }
+        }
+
+        let mut dfa = Dfa::new(0);
+        dfa.extend_alphabet(alphabet.iter().cloned());
+        let mut numbers = HashMap::new();
+        let mut queue = VecDeque::new();
+        if block[0] != block[dead] {
+            numbers.insert(block[0], 0);
+            queue.push_back(block[0]);
+        }
+        while let Some(current) = queue.pop_front() {
+            let number = numbers[&current];
+            let representative = blocks[current][0];
+            if accepting[representative] {
+                dfa.accept(number);
+            }
+            for (symbol, &to) in alphabet.iter().zip(&delta[representative]) {
+                let to = block[to];
+                if to == block[dead] {
+                    continue;
+                }
+                let next = match numbers.get(&to) {
+                    Some(&next) => next,
+                    None => {
+                        let next = numbers.len();
+                        let _ = dfa.add_state(next);
+                        numbers.insert(to, next);
+                        queue.push_back(to);
+                        next
+                    }
+                };
+                let _ = dfa.add_transition(&number, symbol.clone(), &next);
+            }
+        }
+        dfa
+    }
+
+    ///Runs both automata side by side, with missing transitions leading to a rejecting dead state.
+    fn product<Other>(
+        &self,
+        other: &Dfa<Other, Symbol>,
+        accept: fn(bool, bool) -> bool,
+    ) -> Dfa<usize, Symbol>
+    where
+        Other: Eq + Hash + Clone,
+    {
+        let alphabet: Vec<_> = self.alphabet.union(&other.alphabet).cloned().collect();
+        let mut dfa = Dfa::new(0);
+        dfa.extend_alphabet(alphabet.iter().cloned());
+
+        let start = (Some(&self.start), Some(&other.start));
+        let mut numbers = HashMap::new();
+        numbers.insert(start, 0);
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        while let Some(current) = queue.pop_front() {
+            let number = numbers[&current];
+            let (left, right) = current;
+            let accepting = (
+                left.filter(|x| self.is_accepting(x)).is_some(),
+                right.filter(|x| other.is_accepting(x)).is_some(),
+            );
+            if accept(accepting.0, accepting.1) {
+                dfa.accept(number);
+            }
+            for symbol in &alphabet {
+                let next = (
+                    left.and_then(|x| self.transition(x, symbol.clone())),
+                    right.and_then(|x| other.transition(x, symbol.clone())),
+                );
+                if next == (None, None) {
+                    continue;
+                }
+                let to = match numbers.get(&next) {
+                    Some(&to) => to,
+                    None => {
+                        let to = numbers.len();
+                        let _ = dfa.add_state(to);
+                        numbers.insert(next, to);
+                        queue.push_back(next);
+                        to
+                    }
+                };
+                let _ = dfa.add_transition(&number, symbol.clone(), &to);
+            }
+        }
+        dfa
+    }
+
+    ///An automaton accepting the inputs accepted by both automata.
+    pub fn intersection<Other>(&self, other: &Dfa<Other, Symbol>) -> Dfa<usize, Symbol>
+    where
+        Other: Eq + Hash + Clone,
+    {
+        self.product(other, |a, b| a && b)
+    }
+
+    ///An automaton accepting the inputs accepted by either automaton.
+    pub fn union<Other>(&self, other: &Dfa<Other, Symbol>) -> Dfa<usize, Symbol>
+    where
+        Other: Eq + Hash + Clone,
+    {
+        self.product(other, |a, b| a || b)
+    }
+
+    ///An automaton accepting the inputs accepted by this automaton, but not by the other.
+    pub fn difference<Other>(&self, other: &Dfa<Other, Symbol>) -> Dfa<usize, Symbol>
+    where
+        Other: Eq + Hash + Clone,
+    {
+        self.product(other, |a, b| a && !b)
+    }
+}
+
+///A step by step execution of a deterministic automaton. Once a transition is missing, it stays rejected.
+pub struct Execution<'a, State, Symbol>
+where
+    State: Eq + Hash,
+    Symbol: Eq + Hash,
+{
+    automaton: &'a Dfa<State, Symbol>,
+    state: Option<&'a State>,
+}
+
+impl<'a, State, Symbol> Execution<'a, State, Symbol>
+where
+    State: Eq + Hash + Clone,
+    Symbol: Eq + Hash + Clone,
+{
+    ///Follows the transition on the given symbol, returning the state reached.
+    pub fn step(&mut self, symbol: Symbol) -> Option<&'a State> {
+        let automaton = self.automaton;
+        self.state = self.state.and_then(|x| automaton.transition(x, symbol));
+        self.state
+    }
+
+    ///The current state, or None if a transition was missing.
+    pub fn state(&self) -> Option<&'a State> {
+        self.state
+    }
+
+    pub fn is_accepting(&self) -> bool {
+        match self.state {
+            Some(state) => self.automaton.is_accepting(state),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///Binary numbers, read from the most significant bit, which are divisible by three.
+    fn divisible() -> Dfa<u32, char> {
+        let mut dfa = Dfa::new(0);
+        dfa.add_state(1).unwrap();
+        dfa.add_state(2).unwrap();
+        for state in 0..3 {
+            for (bit, symbol) in [(0, '0'), (1, '1')].iter() {
+                dfa.add_transition(&state, *symbol, &((state * 2 + bit) % 3))
+                    .unwrap();
+            }
+        }
+        dfa.accept(0);
+        dfa
+    }
+
+    #[test]
+    fn accepts() {
+        let dfa = divisible();
+        assert!(dfa.accepts("".chars()));
+        assert!(dfa.accepts("110".chars()));
+        assert!(dfa.accepts("1001".chars()));
+        assert!(!dfa.accepts("111".chars()));
+        assert!(!dfa.accepts("12".chars()));
+        assert!(dfa.is_complete());
+    }
+
+    #[test]
+    fn deterministic() {
+        let mut dfa = divisible();
+        assert_eq!(dfa.add_transition(&0, '0', &1), Err('0'));
+        assert_eq!(dfa.add_transition(&0, '2', &7), Err('2'));
+        assert_eq!(dfa.add_state(1), Err(1));
+        assert!(!dfa.accept(7));
+    }
+
+    #[test]
+    fn execution() {
+        let dfa = divisible();
+        let mut run = dfa.run();
+        assert_eq!(run.step('1'), Some(&1));
+        assert_eq!(run.step('1'), Some(&0));
+        assert!(run.is_accepting());
+        assert_eq!(run.step('x'), None);
+        assert_eq!(run.step('0'), None);
+        assert!(!run.is_accepting());
+    }
+
+    #[test]
+    fn complete() {
+        //Strings over a and b which end in ab.
+        let mut dfa = Dfa::new(0);
+        dfa.add_state(1).unwrap();
+        dfa.add_state(2).unwrap();
+        dfa.add_transition(&0, 'a', &1).unwrap();
+        dfa.add_transition(&1, 'b', &2).unwrap();
+        dfa.accept(2);
+        assert!(!dfa.is_complete());
+        assert!(!dfa.accepts("aab".chars()));
+
+        assert_eq!(dfa.complete(1), Err(1));
+        dfa.complete(3).unwrap();
+        assert!(dfa.is_complete());
+        assert_eq!(dfa.transition(&0, 'b'), Some(&3));
+        assert_eq!(dfa.transition(&3, 'a'), Some(&3));
+        assert!(!dfa.accepts("aab".chars()));
+        assert!(dfa.accepts("ab".chars()));
+        assert_eq!(dfa.states().count(), 4);
+    }
+
+    ///Every binary string up to the given length.
+    fn strings(length: usize) -> Vec<String> {
+        let mut strings = vec![String::new()];
+        let mut last = strings.clone();
+        for _ in 0..length {
+            last = last
+                .iter()
+                .flat_map(|x| vec![format!("{}0", x), format!("{}1", x)])
+                .collect();
+            strings.extend(last.iter().cloned());
+        }
+        strings
+    }
+
+    fn value(input: &str) -> u32 {
+        input.chars().fold(0, |x, y| x * 2 + y.to_digit(2).unwrap())
+    }
+
+    #[test]
+    fn minimize() {
+        //Divisibility by three, counting modulo six.
+        let mut dfa = Dfa::new(0);
+        for state in 1..7 {
+            dfa.add_state(state).unwrap();
+        }
+        for state in 0..6 {
+            for (bit, symbol) in [(0, '0'), (1, '1')].iter() {
+                dfa.add_transition(&state, *symbol, &((state * 2 + bit) % 6))
+                    .unwrap();
+            }
+        }
+        dfa.accept(0);
+        dfa.accept(3);
+        //An unreachable accepting state.
+        dfa.add_transition(&6, '0', &0).unwrap();
+        dfa.accept(6);
+
+        let minimal = dfa.minimize();
+        assert_eq!(minimal.states().count(), 3);
+        for input in strings(7) {
+            assert_eq!(
+                minimal.accepts(input.chars()),
+                value(&input).is_multiple_of(3)
+            );
+        }
+
+        let empty = Dfa::<u32, char>::new(0).minimize();
+        assert_eq!(empty.states().count(), 1);
+        assert!(!empty.accepts("".chars()));
+    }
+
+    #[test]
+    fn products() {
+        let three = divisible();
+        //Even binary numbers, with no transition on a leading one from the start.
+        let mut even = Dfa::new("start");
+        even.add_state("even").unwrap();
+        even.add_state("odd").unwrap();
+        even.add_transition(&"start", '0', &"even").unwrap();
+        for state in &["even", "odd"] {
+            even.add_transition(state, '0', &"even").unwrap();
+            even.add_transition(state, '1', &"odd").unwrap();
+        }
+        even.accept("even");
+
+        let intersection = three.intersection(&even);
+        let union = three.union(&even);
+        let difference = three.difference(&even);
+        for input in strings(6) {
+            let a = value(&input).is_multiple_of(3);
+            let b = input.starts_with('0') && input.ends_with('0');
+            assert_eq!(intersection.accepts(input.chars()), a && b, "{}", input);
+            assert_eq!(union.accepts(input.chars()), a || b, "{}", input);
+            assert_eq!(difference.accepts(input.chars()), a && !b, "{}", input);
+        }
+        let minimal = difference.minimize();
+        assert_eq!(
+            minimal.minimize().states().count(),
+            minimal.states().count()
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod dfa;
+pub mod nfa;
+pub mod regex;
+
+pub use dfa::{Dfa, Execution, Transitions};
+pub use nfa::Nfa;
+pub use regex::Regex;
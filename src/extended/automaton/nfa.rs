@@ -0,0 +1,292 @@
+use crate::dev::orientation::{AddEdge, Directed};
+use crate::dev::simple::Simple;
+use crate::dev::{AddVertex, GetVertex, Neighbours, Vertices};
+use crate::extended::automaton::dfa::Dfa;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///Nondeterministic finite automaton, where the states are vertices and the transitions are edges holding their input symbol,
+///or None for epsilon transitions, which are taken without reading any input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nfa<State, Symbol>
+where
+    State: Eq + Hash,
+{
+    graph: Simple<State, (), usize, Option<Symbol>>,
+    start: State,
+    accepting: HashSet<State>,
+}
+
+impl<State, Symbol> Nfa<State, Symbol>
+where
+    State: Eq + Hash + Clone,
+    Symbol: Eq + Hash + Clone,
+{
+    ///Creates an automaton with only the start state, rejecting everything.
+    pub fn new(start: State) -> Self {
+        let mut graph = Simple::default();
+        let _ = graph.add_vertex((start.clone(), ()));
+        Self {
+            graph,
+            start,
+            accepting: HashSet::new(),
+        }
+    }
+
+    pub fn graph(&self) -> &Simple<State, (), usize, Option<Symbol>> {
+        &self.graph
+    }
+
+    pub fn start(&self) -> &State {
+        &self.start
+    }
+
+    pub fn accepting(&self) -> &HashSet<State> {
+        &self.accepting
+    }
+
+    ///The symbols of every transition.
+    pub fn alphabet(&self) -> HashSet<&Symbol> {
+        self.graph.edges.values().flat_map(|x| &x.data).collect()
+    }
+
+    ///Adds a state, returning it back if it already exists.
+    pub fn add_state(&mut self, state: State) -> Result<State, State> {
+        self.graph.add_vertex((state, ())).map_err(|x| x.0)
+    }
+
+    ///Adds a transition on the given symbol, or an epsilon transition for None. Returns the symbol back if either state is missing.
+    pub fn add_transition(
+        &mut self,
+        from: &State,
+        symbol: Option<Symbol>,
+        to: &State,
+    ) -> Result<(), Option<Symbol>> {
+        let key = self.graph.edges.len();
+        AddEdge::<Directed, _, _>::add_edge(&mut self.graph, from, to, (key, symbol))
+            .map(|_| ())
+            .map_err(|x| x.1)
+    }
+
+    ///Marks an existing state as accepting, returning whether it exists.
+    pub fn accept(&mut self, state: State) -> bool {
+        let exists = self.graph.get_vertex(&state).is_some();
+        if exists {
+            self.accepting.insert(state);
+        }
+        exists
+    }
+
+    ///The transitions leaving the given state, as pairs of a symbol, or None for epsilon, and the state reached.
+    pub fn transitions<'a>(
+        &'a self,
+        state: &State,
+    ) -> impl Iterator<Item = (&'a Option<Symbol>, &'a State)> {
+        Neighbours::<Directed, _>::neighbours(&self.graph, state)
+            .into_iter()
+            .flatten()
+            .map(move |(key, to)| (&self.graph.edges[key].data, to))
+    }
+
+    ///The given states, along with every state reachable from them through epsilon transitions.
+    pub fn epsilon_closure<'a, States>(&'a self, states: States) -> HashSet<&'a State>
+    where
+        States: IntoIterator<Item = &'a State>,
+    {
+        let mut closure = HashSet::new();
+        let mut stack: Vec<_> = states.into_iter().collect();
+        while let Some(state) = stack.pop() {
+            if closure.insert(state) {
+                stack.extend(
+                    self.transitions(state)
+                        .filter(|x| x.0.is_none())
+                        .map(|x| x.1),
+                );
+            }
+        }
+        closure
+    }
+
+    ///The states reachable from the given states by reading the symbol, closed under epsilon transitions.
+    pub fn step<'a>(&'a self, states: &HashSet<&'a State>, symbol: &Symbol) -> HashSet<&'a State> {
+        let next: Vec<_> = states
+            .iter()
+            .flat_map(|&state| self.transitions(state))
+            .filter(|x| x.0.as_ref() == Some(symbol))
+            .map(|x| x.1)
+            .collect();
+        self.epsilon_closure(next)
+    }
+
+    ///Whether some sequence of transitions reading the input leads from the start state to an accepting state.
+    pub fn accepts<Input>(&self, input: Input) -> bool
+    where
+        Input: IntoIterator<Item = Symbol>,
+    {
+        let mut states = self.epsilon_closure(Some(&self.start));
+        for symbol in input {
+            if states.is_empty() {
+                return false;
+            }
+            states = self.step(&states, &symbol);
+        }
+        states.iter().any(|x| self.accepting.contains(x))
+    }
+
+    ///The subset construction, where every state of the deterministic automaton is a set of states reachable on the same input.
+    ///The states are numbered from zero, being the start, and the empty set is left out, leaving its transitions missing.
+    pub fn to_dfa(&self) -> Dfa<usize, Symbol> {
+        let keys: Vec<_> = self.graph.vertices().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let subset = |states: HashSet<&State>| {
+            let mut subset: Vec<_> = states.into_iter().map(|x| index[x]).collect();
+            subset.sort_unstable();
+            subset
+        };
+
+        let mut dfa = Dfa::new(0);
+        dfa.extend_alphabet(self.alphabet().into_iter().cloned());
+        let start = subset(self.epsilon_closure(Some(&self.start)));
+        let mut numbers = HashMap::new();
+        numbers.insert(start.clone(), 0);
+        let mut stack = vec![start];
+        while let Some(current) = stack.pop() {
+            let number = numbers[&current];
+            let states: HashSet<_> = current.iter().map(|&x| keys[x]).collect();
+            if states.iter().any(|x| self.accepting.contains(x)) {
+                dfa.accept(number);
+            }
+
+            let mut symbols: HashMap<_, Vec<_>> = HashMap::new();
+            for state in &states {
+                for (symbol, to) in self.transitions(state) {
+                    if let Some(symbol) = symbol {
+                        symbols.entry(symbol).or_default().push(to);
+                    }
+                }
+            }
+            for (symbol, next) in symbols {
+                let next = subset(self.epsilon_closure(next));
+                let to = match numbers.get(&next) {
+                    Some(&to) => to,
+                    None => {
+                        let to = numbers.len();
+                        let _ = dfa.add_state(to);
+                        numbers.insert(next.clone(), to);
+                        stack.push(next);
+                        to
+                    }
+                };
+                let _ = dfa.add_transition(&number, symbol.clone(), &to);
+            }
+        }
+        dfa
+    }
+}
+
+impl<State, Symbol> From<&Dfa<State, Symbol>> for Nfa<State, Symbol>
+where
+    State: Eq + Hash + Clone,
+    Symbol: Eq + Hash + Clone,
+{
+    fn from(dfa: &Dfa<State, Symbol>) -> Self {
+        let mut nfa = Nfa::new(dfa.start().clone());
+        for state in dfa.states() {
+            let _ = nfa.add_state(state.clone());
+        }
+        for state in dfa.states() {
+            for (symbol, to) in dfa.transitions(state) {
+                let _ = nfa.add_transition(state, Some(symbol.clone()), to);
+            }
+        }
+        for state in dfa.accepting() {
+            nfa.accept(state.clone());
+        }
+        nfa
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///Strings over a and b whose third symbol from the end is an a, with an epsilon transition skipping into the loop.
+    fn third_last() -> Nfa<u32, char> {
+        let mut nfa = Nfa::new(0);
+        for state in 1..5 {
+            nfa.add_state(state).unwrap();
+        }
+        nfa.add_transition(&0, None, &1).unwrap();
+        nfa.add_transition(&1, Some('a'), &1).unwrap();
+        nfa.add_transition(&1, Some('b'), &1).unwrap();
+        nfa.add_transition(&1, Some('a'), &2).unwrap();
+        nfa.add_transition(&2, Some('a'), &3).unwrap();
+        nfa.add_transition(&2, Some('b'), &3).unwrap();
+        nfa.add_transition(&3, Some('a'), &4).unwrap();
+        nfa.add_transition(&3, Some('b'), &4).unwrap();
+        nfa.accept(4);
+        nfa
+    }
+
+    fn expected(input: &str) -> bool {
+        input.len() >= 3 && input.chars().rev().nth(2) == Some('a')
+    }
+
+    ///Every string over a and b up to the given length.
+    fn strings(length: usize) -> Vec<String> {
+        let mut strings = vec![String::new()];
+        let mut last = strings.clone();
+        for _ in 0..length {
+            last = last
+                .iter()
+                .flat_map(|x| vec![format!("{}a", x), format!("{}b", x)])
+                .collect();
+            strings.extend(last.iter().cloned());
+        }
+        strings
+    }
+
+    #[test]
+    fn simulation() {
+        let nfa = third_last();
+        for input in strings(6) {
+            assert_eq!(nfa.accepts(input.chars()), expected(&input), "{}", input);
+        }
+        assert!(!nfa.accepts("abc".chars()));
+        assert_eq!(nfa.epsilon_closure(Some(&0)).len(), 2);
+    }
+
+    #[test]
+    fn subset_construction() {
+        let nfa = third_last();
+        let dfa = nfa.to_dfa();
+        //The start set also holds the state before the epsilon transition, which no other set does.
+        assert_eq!(dfa.states().count(), 9);
+        assert!(dfa.is_complete());
+
+        let minimal = dfa.minimize();
+        assert_eq!(minimal.states().count(), 8);
+        for input in strings(6) {
+            assert_eq!(dfa.accepts(input.chars()), expected(&input), "{}", input);
+            assert_eq!(
+                minimal.accepts(input.chars()),
+                expected(&input),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn from_dfa() {
+        let mut dfa = Dfa::new(0);
+        dfa.add_state(1).unwrap();
+        dfa.add_transition(&0, 'a', &1).unwrap();
+        dfa.add_transition(&1, 'b', &0).unwrap();
+        dfa.accept(1);
+        let nfa = Nfa::from(&dfa);
+        assert!(nfa.accepts("aba".chars()));
+        assert!(!nfa.accepts("ab".chars()));
+        assert_eq!(nfa.to_dfa().states().count(), 2);
+    }
+}
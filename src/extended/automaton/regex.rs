@@ -0,0 +1,669 @@
+use crate::dev::Vertices;
+use crate::extended::automaton::dfa::Dfa;
+use crate::extended::automaton::nfa::Nfa;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+///Regular expressions over characters, supporting concatenation, alternation with `|`, repetition with `*`, `+` and `?`,
+///grouping with parentheses and character classes such as `[a-z_]`. Special characters are escaped with a backslash.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Regex {
+    ///Matches only the empty string, written as `()`.
+    Epsilon,
+    ///Matches the character.
+    Symbol(char),
+    ///Matches a character within any of the inclusive ranges. Without ranges, written as `[]`, it matches nothing.
+    Class(Vec<(char, char)>),
+    Concatenation(Vec<Regex>),
+    Alternation(Vec<Regex>),
+    Star(Box<Regex>),
+    Plus(Box<Regex>),
+    Optional(Box<Regex>),
+}
+
+///The deepest expression a parsed pattern may give, as well as the most groups open at once, as both are processed recursively.
+pub const MAXIMUM_DEPTH: usize = 256;
+
+///The most characters a parsed class may match, as each of them becomes a transition of the automaton.
+pub const MAXIMUM_CLASS_WIDTH: u32 = 1024;
+
+const SPECIAL: &str = "()[]|*+?\\";
+const SPECIAL_CLASS: &str = "[]-\\";
+
+impl Regex {
+    ///Parses the pattern, or returns the position of the first character which could not be parsed,
+    ///being the length of the pattern when it ends early. Patterns nesting deeper than MAXIMUM_DEPTH, or with a class
+    ///wider than MAXIMUM_CLASS_WIDTH, fail at the character exceeding the limit.
+    pub fn parse(pattern: &str) -> Result<Self, usize> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            position: 0,
+            groups: 0,
+        };
+        let (regex, _) = parser.alternation()?;
+        if parser.position < parser.chars.len() {
+            return Err(parser.position);
+        }
+        Ok(regex)
+    }
+
+    ///Thompson's construction, where every part of the expression becomes a piece of automaton between two states,
+    ///joined to the other pieces through epsilon transitions. The start is state 0, and the only accepting state is 1.
+    pub fn to_nfa(&self) -> Nfa<usize, char> {
+        let mut nfa = Nfa::new(0);
+        let _ = nfa.add_state(1);
+        nfa.accept(1);
+        self.thompson(&mut nfa, 0, 1);
+        nfa
+    }
+
+    ///The minimal deterministic automaton, through the subset construction of the automaton from `to_nfa`.
+    pub fn to_dfa(&self) -> Dfa<usize, char> {
+        self.to_nfa().to_dfa().minimize()
+    }
+
+    fn thompson(&self, nfa: &mut Nfa<usize, char>, from: usize, to: usize) {
+        let fresh = |nfa: &mut Nfa<usize, char>| {
+            let state = nfa.graph().vertices.len();
+            let _ = nfa.add_state(state);
+            state
+        };
+        match self {
+            Regex::Epsilon => {
+                let _ = nfa.add_transition(&from, None, &to);
+            }
+            Regex::Symbol(symbol) => {
+                let _ = nfa.add_transition(&from, Some(*symbol), &to);
+            }
+            Regex::Class(ranges) => {
+                for &(low, high) in ranges {
+                    for symbol in low..=high {
+                        let _ = nfa.add_transition(&from, Some(symbol), &to);
+                    }
+                }
+            }
+            Regex::Concatenation(parts) => {
+                let mut at = from;
+                for (i, part) in parts.iter().enumerate() {
+                    let next = if i + 1 == parts.len() { to } else { fresh(nfa) };
+                    part.thompson(nfa, at, next);
+                    at = next;
+                }
+                if parts.is_empty() {
+                    let _ = nfa.add_transition(&from, None, &to);
+                }
+            }
+            Regex::Alternation(alternatives) => {
+                for alternative in alternatives {
+                    let (start, end) = (fresh(nfa), fresh(nfa));
+                    let _ = nfa.add_transition(&from, None, &start);
+                    alternative.thompson(nfa, start, end);
+                    let _ = nfa.add_transition(&end, None, &to);
+                }
+            }
+            Regex::Star(inner) | Regex::Plus(inner) | Regex::Optional(inner) => {
+                let (start, end) = (fresh(nfa), fresh(nfa));
+                let _ = nfa.add_transition(&from, None, &start);
+                inner.thompson(nfa, start, end);
+                let _ = nfa.add_transition(&end, None, &to);
+                if !matches!(self, Regex::Plus(_)) {
+                    let _ = nfa.add_transition(&from, None, &to);
+                }
+                if !matches!(self, Regex::Optional(_)) {
+                    let _ = nfa.add_transition(&end, None, &start);
+                }
+            }
+        }
+    }
+
+    ///How tightly the expression binds, from alternation up to single characters and groups.
+    fn precedence(&self) -> usize {
+        match self {
+            Regex::Alternation(x) if x.len() > 1 => 0,
+            Regex::Star(_) | Regex::Plus(_) | Regex::Optional(_) => 2,
+            Regex::Epsilon | Regex::Symbol(_) | Regex::Class(_) => 3,
+            _ => 1,
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, precedence: usize) -> fmt::Result {
+        if self.precedence() < precedence {
+            write!(f, "(")?;
+            self.write(f, 0)?;
+            return write!(f, ")");
+        }
+        let escape = |f: &mut fmt::Formatter<'_>, symbol: char, special: &str| {
+            if special.contains(symbol) {
+                write!(f, "\\")?;
+            }
+            write!(f, "{}", symbol)
+        };
+        match self {
+            Regex::Epsilon => write!(f, "()"),
+            Regex::Symbol(symbol) => escape(f, *symbol, SPECIAL),
+            Regex::Class(ranges) => {
+                write!(f, "[")?;
+                for &(low, high) in ranges {
+                    escape(f, low, SPECIAL_CLASS)?;
+                    if low != high {
+                        write!(f, "-")?;
+                        escape(f, high, SPECIAL_CLASS)?;
+                    }
+                }
+                write!(f, "]")
+            }
+            Regex::Concatenation(parts) => parts.iter().try_for_each(|x| x.write(f, 2)),
+            Regex::Alternation(alternatives) => {
+                for (i, alternative) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+                    alternative.write(f, 1)?;
+                }
+                Ok(())
+            }
+            Regex::Star(inner) => {
+                inner.write(f, 3)?;
+                write!(f, "*")
+            }
+            Regex::Plus(inner) => {
+                inner.write(f, 3)?;
+                write!(f, "+")
+            }
+            Regex::Optional(inner) => {
+                inner.write(f, 3)?;
+                write!(f, "?")
+            }
+        }
+    }
+}
+
+impl FromStr for Regex {
+    type Err = usize;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Regex::parse(pattern)
+    }
+}
+
+impl fmt::Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+///Recursive descent over the characters of a pattern, from the loosest binding operator to the tightest.
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    //The groups open at the current position.
+    groups: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Result<char, usize> {
+        let next = self.peek().ok_or(self.position)?;
+        self.position += 1;
+        Ok(next)
+    }
+
+    ///A character, which may be escaped by a backslash.
+    fn symbol(&mut self) -> Result<char, usize> {
+        match self.next()? {
+            '\\' => self.next(),
+            symbol => Ok(symbol),
+        }
+    }
+
+    ///The depth of an expression holding parts of the given depths, failing at the current position when too deep.
+    fn nest<Depths>(&self, depths: Depths) -> Result<usize, usize>
+    where
+        Depths: IntoIterator<Item = usize>,
+    {
+        let depth = depths.into_iter().max().unwrap_or(0) + 1;
+        if depth > MAXIMUM_DEPTH {
+            Err(self.position)
+        } else {
+            Ok(depth)
+        }
+    }
+
+    //Each of the following returns the expression along with its depth.
+
+    fn alternation(&mut self) -> Result<(Regex, usize), usize> {
+        let mut alternatives = vec![self.concatenation()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            alternatives.push(self.concatenation()?);
+        }
+        if alternatives.len() == 1 {
+            return Ok(alternatives.pop().unwrap());
+        }
+        let depth = self.nest(alternatives.iter().map(|x| x.1))?;
+        let alternatives = alternatives.into_iter().map(|x| x.0).collect();
+        Ok((Regex::Alternation(alternatives), depth))
+    }
+
+    fn concatenation(&mut self) -> Result<(Regex, usize), usize> {
+        let mut parts = Vec::new();
+        while let Some(next) = self.peek() {
+            if next == '|' || next == ')' {
+                break;
+            }
+            parts.push(self.repetition()?);
+        }
+        match parts.len() {
+            0 => Ok((Regex::Epsilon, 1)),
+            1 => Ok(parts.pop().unwrap()),
+            _ => {
+                let depth = self.nest(parts.iter().map(|x| x.1))?;
+                let parts = parts.into_iter().map(|x| x.0).collect();
+                Ok((Regex::Concatenation(parts), depth))
+            }
+        }
+    }
+
+    fn repetition(&mut self) -> Result<(Regex, usize), usize> {
+        let (mut regex, mut depth) = self.atom()?;
+        while let Some(next) = self.peek() {
+            regex = match next {
+                '*' => Regex::Star(Box::new(regex)),
+                '+' => Regex::Plus(Box::new(regex)),
+                '?' => Regex::Optional(Box::new(regex)),
+                _ => break,
+            };
+            depth = self.nest(Some(depth))?;
+            self.position += 1;
+        }
+        Ok((regex, depth))
+    }
+
+    fn atom(&mut self) -> Result<(Regex, usize), usize> {
+        let position = self.position;
+        match self.peek().ok_or(position)? {
+            '(' => {
+                //Groups add no expression of their own, but bound the recursion of the parser.
+                if self.groups == MAXIMUM_DEPTH {
+                    return Err(position);
+                }
+                self.position += 1;
+                self.groups += 1;
+                //The alternation only stops at the closing parenthesis, or the end.
+                let inner = self.alternation()?;
+                self.groups -= 1;
+                self.next()?;
+                Ok(inner)
+            }
+            '[' => {
+                self.position += 1;
+                Ok((self.class()?, 1))
+            }
+            ')' | ']' | '|' | '*' | '+' | '?' => Err(position),
+            _ => Ok((Regex::Symbol(self.symbol()?), 1)),
+        }
+    }
+
+    ///The ranges of a class, with a dash before the closing bracket taken as itself.
+    fn class(&mut self) -> Result<Regex, usize> {
+        let mut ranges = Vec::new();
+        let mut width = 0;
+        while self.peek().ok_or(self.position)? != ']' {
+            let start = self.position;
+            let low = self.symbol()?;
+            let high =
+                if self.peek() == Some('-') && self.chars.get(self.position + 1) != Some(&']') {
+                    self.position += 1;
+                    let position = self.position;
+                    let high = self.symbol()?;
+                    if high < low {
+                        return Err(position);
+                    }
+                    high
+                } else {
+                    low
+                };
+            width += high as u32 - low as u32 + 1;
+            if width > MAXIMUM_CLASS_WIDTH {
+                return Err(start);
+            }
+            ranges.push((low, high));
+        }
+        self.position += 1;
+        Ok(Regex::Class(ranges))
+    }
+}
+
+///Matches nothing.
+fn nothing() -> Regex {
+    Regex::Class(Vec::new())
+}
+
+fn is_nothing(regex: &Regex) -> bool {
+    match regex {
+        Regex::Class(ranges) => ranges.is_empty(),
+        _ => false,
+    }
+}
+
+///The ranges of expressions matching a single character.
+fn ranges(regex: &Regex) -> Option<Vec<(char, char)>> {
+    match regex {
+        Regex::Symbol(symbol) => Some(vec![(*symbol, *symbol)]),
+        Regex::Class(ranges) => Some(ranges.clone()),
+        _ => None,
+    }
+}
+
+///The alternation of both expressions, simplified where either is trivial or both match single characters.
+fn union(a: Regex, b: Regex) -> Regex {
+    if is_nothing(&a) || a == b {
+        return b;
+    }
+    if is_nothing(&b) {
+        return a;
+    }
+    if let (Some(mut left), Some(right)) = (ranges(&a), ranges(&b)) {
+        left.extend(right);
+        return Regex::Class(left);
+    }
+    match (a, b) {
+        (Regex::Epsilon, x) | (x, Regex::Epsilon) => match x {
+            Regex::Star(_) | Regex::Optional(_) => x,
+            Regex::Plus(inner) => Regex::Star(inner),
+            x => Regex::Optional(Box::new(x)),
+        },
+        (a, b) => {
+            let mut alternatives = Vec::new();
+            for x in [a, b] {
+                match x {
+                    Regex::Alternation(inner) => alternatives.extend(inner),
+                    x => alternatives.push(x),
+                }
+            }
+            Regex::Alternation(alternatives)
+        }
+    }
+}
+
+///The concatenation of both expressions, where an expression next to its own star becomes a plus.
+fn concatenate(a: Regex, b: Regex) -> Regex {
+    if is_nothing(&a) || is_nothing(&b) {
+        return nothing();
+    }
+    let mut parts = Vec::new();
+    for x in [a, b] {
+        match x {
+            Regex::Epsilon => {}
+            Regex::Concatenation(inner) => parts.extend(inner),
+            x => parts.push(x),
+        }
+    }
+    let mut simplified: Vec<Regex> = Vec::new();
+    for part in parts {
+        let plus = match (simplified.last(), &part) {
+            (Some(Regex::Star(inner)), last) | (Some(last), Regex::Star(inner)) => **inner == *last,
+            _ => false,
+        };
+        if plus {
+            let last = simplified.pop().unwrap();
+            let inner = match (last, part) {
+                (Regex::Star(inner), _) | (_, Regex::Star(inner)) => inner,
+                _ => unreachable!(),
+            };
+            simplified.push(Regex::Plus(inner));
+        } else {
+            simplified.push(part);
+        }
+    }
+    match simplified.len() {
+        0 => Regex::Epsilon,
+        1 => simplified.pop().unwrap(),
+        _ => Regex::Concatenation(simplified),
+    }
+}
+
+fn star(regex: Regex) -> Regex {
+    match regex {
+        Regex::Epsilon => Regex::Epsilon,
+        Regex::Star(inner) | Regex::Plus(inner) | Regex::Optional(inner) => Regex::Star(inner),
+        x if is_nothing(&x) => Regex::Epsilon,
+        x => Regex::Star(Box::new(x)),
+    }
+}
+
+impl<State> Nfa<State, char>
+where
+    State: Eq + Hash + Clone,
+{
+    ///An expression matching the accepted inputs, found by state elimination. The transitions become expressions between
+    ///a new start and end, after which the states are removed one at a time, fewest paths through first, by joining every
+    ///transition into the state with every transition out of it, around its loop.
+    pub fn to_regex(&self) -> Regex {
+        let keys: Vec<_> = self.graph().vertices().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let (start, end) = (keys.len(), keys.len() + 1);
+
+        let mut edges: HashMap<(usize, usize), Regex> = HashMap::new();
+        let add = |edges: &mut HashMap<_, _>, key, regex| {
+            let regex = match edges.remove(&key) {
+                Some(existing) => union(existing, regex),
+                None => regex,
+            };
+            edges.insert(key, regex);
+        };
+        add(&mut edges, (start, index[self.start()]), Regex::Epsilon);
+        for state in self.accepting() {
+            add(&mut edges, (index[state], end), Regex::Epsilon);
+        }
+        for (i, &from) in keys.iter().enumerate() {
+            for (symbol, to) in self.transitions(from) {
+                let regex = match symbol {
+                    Some(symbol) => Regex::Symbol(*symbol),
+                    None => Regex::Epsilon,
+                };
+                add(&mut edges, (i, index[to]), regex);
+            }
+        }
+
+        let mut remaining: Vec<_> = (0..keys.len()).collect();
+        while !remaining.is_empty() {
+            let paths = |state: usize| {
+                let incoming = edges
+                    .keys()
+                    .filter(|x| x.1 == state && x.0 != state)
+                    .count();
+                let outgoing = edges
+                    .keys()
+                    .filter(|x| x.0 == state && x.1 != state)
+                    .count();
+                incoming * outgoing
+            };
+            let (i, &state) = remaining
+                .iter()
+                .enumerate()
+                .min_by_key(|x| paths(*x.1))
+                .unwrap();
+            remaining.swap_remove(i);
+
+            let around = star(edges.remove(&(state, state)).unwrap_or_else(nothing));
+            let incoming: Vec<_> = edges.keys().filter(|x| x.1 == state).cloned().collect();
+            let outgoing: Vec<_> = edges.keys().filter(|x| x.0 == state).cloned().collect();
+            let incoming: Vec<_> = incoming
+                .into_iter()
+                .map(|x| (x.0, edges.remove(&x).unwrap()))
+                .collect();
+            let outgoing: Vec<_> = outgoing
+                .into_iter()
+                .map(|x| (x.1, edges.remove(&x).unwrap()))
+                .collect();
+            for (from, into) in &incoming {
+                for (to, out) in &outgoing {
+                    let path = concatenate(concatenate(into.clone(), around.clone()), out.clone());
+                    add(&mut edges, (*from, *to), path);
+                }
+            }
+        }
+        edges.remove(&(start, end)).unwrap_or_else(nothing)
+    }
+}
+
+impl<State> Dfa<State, char>
+where
+    State: Eq + Hash + Clone,
+{
+    ///An expression matching the accepted inputs, found by state elimination.
+    pub fn to_regex(&self) -> Regex {
+        Nfa::from(self).to_regex()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///Every string over the characters up to the given length.
+    fn strings(alphabet: &[char], length: usize) -> Vec<String> {
+        let mut strings = vec![String::new()];
+        let mut last = strings.clone();
+        for _ in 0..length {
+            last = last
+                .iter()
+                .flat_map(|x| alphabet.iter().map(move |y| format!("{}{}", x, y)))
+                .collect();
+            strings.extend(last.iter().cloned());
+        }
+        strings
+    }
+
+    fn assert_language<F>(regex: &Regex, alphabet: &[char], expected: F)
+    where
+        F: Fn(&str) -> bool,
+    {
+        let nfa = regex.to_nfa();
+        let dfa = regex.to_dfa();
+        for input in strings(alphabet, 6) {
+            assert_eq!(nfa.accepts(input.chars()), expected(&input), "{}", input);
+            assert_eq!(dfa.accepts(input.chars()), expected(&input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let regex = Regex::parse("a(b|c)*d?").unwrap();
+        assert_eq!(
+            regex,
+            Regex::Concatenation(vec![
+                Regex::Symbol('a'),
+                Regex::Star(Box::new(Regex::Alternation(vec![
+                    Regex::Symbol('b'),
+                    Regex::Symbol('c')
+                ]))),
+                Regex::Optional(Box::new(Regex::Symbol('d'))),
+            ])
+        );
+        assert_eq!(regex.to_string(), "a(b|c)*d?");
+        assert_eq!(
+            "[a-c_\\-]".parse(),
+            Ok(Regex::Class(vec![('a', 'c'), ('_', '_'), ('-', '-')]))
+        );
+        assert_eq!(
+            "\\*|".parse(),
+            Ok(Regex::Alternation(vec![Regex::Symbol('*'), Regex::Epsilon]))
+        );
+
+        assert_eq!(Regex::parse("a(b"), Err(3));
+        assert_eq!(Regex::parse("ab)"), Err(2));
+        assert_eq!(Regex::parse("*a"), Err(0));
+        assert_eq!(Regex::parse("[z-a]"), Err(3));
+        assert_eq!(Regex::parse("[ab"), Err(3));
+        assert_eq!(Regex::parse("a\\"), Err(2));
+    }
+
+    #[test]
+    fn limits() {
+        let nested = "(".repeat(MAXIMUM_DEPTH) + "a" + &")".repeat(MAXIMUM_DEPTH);
+        assert!(Regex::parse(&nested).is_ok());
+        let deeper = format!("({})", nested);
+        assert_eq!(Regex::parse(&deeper), Err(MAXIMUM_DEPTH));
+        let repeated = "a".to_string() + &"*".repeat(MAXIMUM_DEPTH - 1);
+        assert!(Regex::parse(&repeated)
+            .unwrap()
+            .to_nfa()
+            .accepts("aa".chars()));
+        assert_eq!(Regex::parse(&(repeated + "*")), Err(MAXIMUM_DEPTH));
+
+        //Every group adds one more star than the one within, such that no group is deep on its own.
+        let mut staircase = "a".to_string();
+        for stars in 1..=MAXIMUM_DEPTH {
+            staircase = format!("({}){}", staircase, "*".repeat(stars));
+        }
+        let position = Regex::parse(&staircase).unwrap_err();
+        assert_eq!(staircase.chars().nth(position), Some('*'));
+
+        let wide = format!(
+            "[a-z\u{0}-{}]",
+            char::from_u32(MAXIMUM_CLASS_WIDTH).unwrap()
+        );
+        assert_eq!(Regex::parse(&wide), Err(4));
+        assert_eq!(Regex::parse("[\u{0}-\u{10ffff}]"), Err(1));
+    }
+
+    #[test]
+    fn compile() {
+        let regex = Regex::parse("a(b|c)*d?").unwrap();
+        assert_language(&regex, &['a', 'b', 'c', 'd'], |x| {
+            let body = x.strip_suffix('d').unwrap_or(x);
+            body.starts_with('a') && body[1..].chars().all(|y| y == 'b' || y == 'c')
+        });
+        //The start, the loop over b and c, and the end after d.
+        assert_eq!(regex.to_dfa().states().count(), 3);
+
+        let regex = Regex::parse("[0-9]+(\\.[0-9]+)?").unwrap();
+        assert_language(&regex, &['1', '.'], |x| {
+            let parts: Vec<_> = x.split('.').collect();
+            parts.len() <= 2 && parts.iter().all(|y| !y.is_empty())
+        });
+
+        assert_language(&Regex::parse("").unwrap(), &['a'], str::is_empty);
+        assert_language(&Regex::parse("[]").unwrap(), &['a'], |_| false);
+        assert_language(&Regex::parse("(a|())+").unwrap(), &['a', 'b'], |x| {
+            !x.contains('b')
+        });
+    }
+
+    #[test]
+    fn state_elimination() {
+        for pattern in &[
+            "a(b|c)*d?",
+            "(ab|b)*a?",
+            "((a|b)(a|b))*",
+            "[]",
+            "",
+            "a*b+|ba",
+        ] {
+            let regex = Regex::parse(pattern).unwrap();
+            let dfa = regex.to_dfa();
+            let eliminated = dfa.to_regex();
+            let parsed = Regex::parse(&eliminated.to_string()).unwrap();
+            assert_eq!(parsed, eliminated, "{}", pattern);
+
+            let expected = regex.to_nfa();
+            assert_language(&eliminated, &['a', 'b', 'c', 'd'], |x| {
+                expected.accepts(x.chars())
+            });
+        }
+
+        //A single looping state.
+        let mut dfa = Dfa::new(0);
+        dfa.add_transition(&0, 'a', &0).unwrap();
+        dfa.accept(0);
+        assert_eq!(dfa.to_regex().to_string(), "a*");
+        assert_eq!(Dfa::<u8, char>::new(0).to_regex().to_string(), "[]");
+    }
+}
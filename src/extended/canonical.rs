@@ -0,0 +1,430 @@
+use crate::dev::{orientation, GetEdge, GetVertex, Neighbours, Vertices};
+use crate::extended::find;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+///64 bit FNV-1a, used instead of the standard hasher, as its output is not guaranteed to be stable.
+///Integers are written as little endian, with `usize` and `isize` widened to 64 bits, such that the standard `Hash`
+///implementations of the vertex and edge data hash equally on every target.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_i16(&mut self, value: i16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i128(&mut self, value: i128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+}
+
+fn stable_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = Fnv::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+///A canonical ordering of the vertices of a graph, such that isomorphic graphs end up with identical certificates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Canonical<'a, VertexKey> {
+    ///The vertices of the graph in canonical order.
+    pub order: Vec<&'a VertexKey>,
+    certificate: Vec<u64>,
+}
+
+impl<'a, VertexKey> Canonical<'a, VertexKey> {
+    ///The vertex labels and edges, relative to the canonical order. Equal exactly when the graphs are isomorphic.
+    pub fn certificate(&self) -> &[u64] {
+        &self.certificate
+    }
+
+    ///A stable 64 bit hash of the certificate, hashing every entry as eight little endian bytes.
+    pub fn hash(&self) -> u64 {
+        let mut hasher = Fnv::default();
+        for entry in &self.certificate {
+            hasher.write(&entry.to_le_bytes());
+        }
+        hasher.finish()
+    }
+}
+
+///Vertex labels and edge labels hashed, with edges running in both directions.
+struct Labelled {
+    vertices: Vec<u64>,
+    outgoing: Vec<Vec<(usize, u64)>>,
+    incoming: Vec<Vec<(usize, u64)>>,
+}
+
+impl Labelled {
+    ///Splits the color classes by the colors of their neighbours, until the partition is stable.
+    fn refine(&self, colors: &mut Vec<u64>) {
+        let mut classes = distinct(colors);
+        loop {
+            let signatures: Vec<_> = (0..colors.len())
+                .map(|vertex| {
+                    let neighbours = |edges: &Vec<(usize, u64)>| {
+                        let mut neighbours: Vec<_> =
+                            edges.iter().map(|(x, edge)| (colors[*x], *edge)).collect();
+                        neighbours.sort_unstable();
+                        neighbours
+                    };
+                    (
+                        colors[vertex],
+                        neighbours(&self.outgoing[vertex]),
+                        neighbours(&self.incoming[vertex]),
+                    )
+                })
+                .collect();
+
+            let mut sorted: Vec<_> = signatures.iter().collect();
+            sorted.sort_unstable();
+            sorted.dedup();
+            let rank: HashMap<_, _> = sorted
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| (x, i as u64))
+                .collect();
+            let refined: Vec<_> = signatures.iter().map(|x| rank[x]).collect();
+
+            *colors = refined;
+            let count = distinct(colors);
+            if count == classes {
+                break;
+            }
+            classes = count;
+        }
+    }
+
+    fn certificate(&self, order: &[usize]) -> Vec<u64> {
+        let mut position = vec![0; order.len()];
+        for (i, &vertex) in order.iter().enumerate() {
+            position[vertex] = i;
+        }
+        let mut edges: Vec<_> = (0..order.len())
+            .flat_map(|from| {
+                self.outgoing[from]
+                    .iter()
+                    .map(move |(to, edge)| (from, *to, *edge))
+            })
+            .map(|(from, to, edge)| (position[from] as u64, position[to] as u64, edge))
+            .collect();
+        edges.sort_unstable();
+
+        let mut certificate = vec![order.len() as u64];
+        certificate.extend(order.iter().map(|&x| self.vertices[x]));
+        certificate.push(edges.len() as u64);
+        for (from, to, edge) in edges {
+            certificate.extend(&[from, to, edge]);
+        }
+        certificate
+    }
+}
+
+fn distinct(colors: &[u64]) -> usize {
+    let mut colors = colors.to_vec();
+    colors.sort_unstable();
+    colors.dedup();
+    colors.len()
+}
+
+///Individualization-refinement search for the ordering with the smallest certificate.
+struct Search<'b> {
+    graph: &'b Labelled,
+    best: Option<(Vec<u64>, Vec<usize>)>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<'b> Search<'b> {
+    ///Checks whether some known automorphism, fixing every individualized vertex, maps one vertex onto the other.
+    fn same_orbit(&self, a: usize, b: usize, fixed: &[usize]) -> bool {
+        let mut parent: Vec<_> = (0..self.graph.vertices.len()).collect();
+        for automorphism in &self.automorphisms {
+            if fixed.iter().all(|&x| automorphism[x] == x) {
+                for (from, &to) in automorphism.iter().enumerate() {
+                    let (from, to) = (find(&mut parent, from), find(&mut parent, to));
+                    parent[from] = to;
+                }
+            }
+        }
+        find(&mut parent, a) == find(&mut parent, b)
+    }
+
+    fn leaf(&mut self, colors: &[u64]) {
+        let mut order: Vec<_> = (0..colors.len()).collect();
+        order.sort_by_key(|&x| colors[x]);
+        let certificate = self.graph.certificate(&order);
+
+        match &self.best {
+            Some((best, best_order)) if *best == certificate => {
+                let mut automorphism = vec![0; order.len()];
+                for (&from, &to) in best_order.iter().zip(order.iter()) {
+                    automorphism[from] = to;
+                }
+                self.automorphisms.push(automorphism);
+            }
+            Some((best, _)) if *best < certificate => {}
+            _ => self.best = Some((certificate, order)),
+        }
+    }
+
+    fn search(&mut self, mut colors: Vec<u64>, fixed: &mut Vec<usize>) {
+        self.graph.refine(&mut colors);
+
+        let mut count: HashMap<u64, usize> = HashMap::new();
+        for color in &colors {
+            *count.entry(*color).or_default() += 1;
+        }
+        let target = match count.iter().filter(|(_, &x)| x > 1).map(|x| *x.0).min() {
+            Some(target) => target,
+            None => return self.leaf(&colors),
+        };
+
+        let mut explored: Vec<usize> = Vec::new();
+        for vertex in (0..colors.len()).filter(|&x| colors[x] == target) {
+            if explored.iter().any(|&x| self.same_orbit(x, vertex, fixed)) {
+                continue;
+            }
+            explored.push(vertex);
+
+            let individualized = (0..colors.len())
+                .map(|x| 2 * colors[x] + if x == vertex { 0 } else { 1 })
+                .collect();
+            fixed.push(vertex);
+            self.search(individualized, fixed);
+            fixed.pop();
+        }
+    }
+}
+
+///Computes a canonical labelling through color refinement, breaking ties by individualizing vertices.
+pub trait Canonize<'a, VertexKey, Orientation>
+where
+    VertexKey: 'a,
+{
+    fn canonical(&'a self) -> Canonical<'a, VertexKey>;
+
+    ///A stable hash, equal for isomorphic graphs with equal vertex and edge data.
+    fn structural_hash(&'a self) -> u64 {
+        self.canonical().hash()
+    }
+}
+
+impl<'a, VertexKey, EdgeKey, Orientation, Graph> Canonize<'a, VertexKey, Orientation> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Orientation: orientation::Orientation,
+    Graph: Vertices<'a, Item = VertexKey>
+        + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>
+        + GetVertex<VertexKey>
+        + GetEdge<EdgeKey>,
+    <Graph as GetVertex<VertexKey>>::Output: Hash,
+    <Graph as GetEdge<EdgeKey>>::Output: Hash,
+{
+    fn canonical(&'a self) -> Canonical<'a, VertexKey> {
+        let keys: Vec<_> = self.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+
+        let mut graph = Labelled {
+            vertices: keys
+                .iter()
+                .map(|x| stable_hash(&self.get_vertex(x)))
+                .collect(),
+            outgoing: vec![Vec::new(); keys.len()],
+            incoming: vec![Vec::new(); keys.len()],
+        };
+        for (from, key) in keys.iter().enumerate() {
+            for (edge, to) in self.neighbours(key).into_iter().flatten() {
+                if let Some(&to) = index.get(to) {
+                    let label = stable_hash(&self.get_edge(edge));
+                    graph.outgoing[from].push((to, label));
+                    graph.incoming[to].push((from, label));
+                }
+            }
+        }
+
+        let mut search = Search {
+            graph: &graph,
+            best: None,
+            automorphisms: Vec::new(),
+        };
+        search.search(graph.vertices.clone(), &mut Vec::new());
+
+        let (certificate, order) = search
+            .best
+            .unwrap_or_else(|| (graph.certificate(&[]), Vec::new()));
+        Canonical {
+            order: order.into_iter().map(|x| keys[x]).collect(),
+            certificate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::{AddEdge, Directed, Undirected};
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn graph<O: orientation::Orientation>(
+        orientation: O,
+        vertices: &[(i32, char)],
+        edges: &[(i32, i32, u8)],
+    ) -> Oriented<Simple<i32, char, usize, u8>, O>
+    where
+        Simple<i32, char, usize, u8>: AddEdge<O, i32, (usize, u8), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for vertex in vertices {
+            graph.add_vertex(*vertex).unwrap();
+        }
+        for (i, (from, to, label)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *label)).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn relabelled() {
+        let a = graph(
+            Undirected,
+            &[(0, 'a'), (1, 'a'), (2, 'a'), (3, 'a')],
+            &[(0, 1, 0), (1, 2, 0), (2, 3, 0), (1, 3, 0)],
+        );
+        let b = graph(
+            Undirected,
+            &[(10, 'a'), (11, 'a'), (12, 'a'), (13, 'a')],
+            &[(13, 12, 0), (12, 11, 0), (11, 10, 0), (12, 10, 0)],
+        );
+
+        let (ca, cb) = (a.canonical(), b.canonical());
+        assert_eq!(ca.certificate(), cb.certificate());
+        assert_eq!(a.structural_hash(), b.structural_hash());
+    }
+
+    #[test]
+    fn not_isomorphic() {
+        let vertices = [(0, 'a'), (1, 'a'), (2, 'a'), (3, 'a')];
+        let path = graph(Undirected, &vertices, &[(0, 1, 0), (1, 2, 0), (2, 3, 0)]);
+        let star = graph(Undirected, &vertices, &[(0, 1, 0), (0, 2, 0), (0, 3, 0)]);
+
+        assert_ne!(path.structural_hash(), star.structural_hash());
+    }
+
+    #[test]
+    fn labels() {
+        let a = graph(Undirected, &[(0, 'a'), (1, 'b')], &[(0, 1, 0)]);
+        let b = graph(Undirected, &[(0, 'b'), (1, 'a')], &[(1, 0, 0)]);
+        let c = graph(Undirected, &[(0, 'a'), (1, 'a')], &[(0, 1, 0)]);
+        let d = graph(Undirected, &[(0, 'a'), (1, 'b')], &[(0, 1, 1)]);
+
+        assert_eq!(a.structural_hash(), b.structural_hash());
+        assert_ne!(a.structural_hash(), c.structural_hash());
+        assert_ne!(a.structural_hash(), d.structural_hash());
+
+        let labels = |graph: &Oriented<Simple<i32, char, usize, u8>, Undirected>| {
+            let order = graph.canonical().order;
+            order
+                .into_iter()
+                .map(|x| *graph.get_vertex(x).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&a), labels(&b));
+    }
+
+    #[test]
+    fn stable() {
+        let canonical: Canonical<i32> = Canonical {
+            order: Vec::new(),
+            certificate: vec![1, 2, 3],
+        };
+        assert_eq!(canonical.hash(), 0xda2b_fb22_5e0d_1f05);
+        assert_eq!(stable_hash(&[1usize, 2]), stable_hash(&[1u64, 2]));
+        assert_eq!(stable_hash(&-1isize), stable_hash(&-1i64));
+    }
+
+    #[test]
+    fn directed() {
+        let vertices = [(0, 'a'), (1, 'a'), (2, 'a')];
+        let cycle = graph(Directed, &vertices, &[(0, 1, 0), (1, 2, 0), (2, 0, 0)]);
+        let reversed = graph(Directed, &vertices, &[(1, 0, 0), (2, 1, 0), (0, 2, 0)]);
+        let acyclic = graph(Directed, &vertices, &[(0, 1, 0), (1, 2, 0), (0, 2, 0)]);
+
+        assert_eq!(cycle.structural_hash(), reversed.structural_hash());
+        assert_ne!(cycle.structural_hash(), acyclic.structural_hash());
+    }
+
+    #[test]
+    fn symmetric() {
+        let vertices: Vec<_> = (0..8).map(|x| (x, 'a')).collect();
+        let mut edges = Vec::new();
+        for i in 0..8 {
+            for j in 0..i {
+                edges.push((i, j, 0));
+            }
+        }
+        let complete = graph(Undirected, &vertices, &edges);
+        let rotated = graph(
+            Undirected,
+            &vertices,
+            &edges
+                .iter()
+                .map(|(a, b, x)| ((a + 3) % 8, (b + 3) % 8, *x))
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(complete.canonical().order.len(), 8);
+        assert_eq!(complete.structural_hash(), rotated.structural_hash());
+    }
+}
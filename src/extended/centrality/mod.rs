@@ -0,0 +1,5 @@
+pub mod pagerank;
+pub mod path;
+
+pub use pagerank::{Dangling, PageRank, PageRanking};
+pub use path::{Centrality, PathCentrality};
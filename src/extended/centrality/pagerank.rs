@@ -0,0 +1,271 @@
+use crate::dev::orientation::Directed;
+use crate::dev::{GetEdge, Neighbours, Vertices};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+///Where the rank of vertices without outgoing edges is sent.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Dangling<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///Spread evenly across every vertex.
+    Uniform,
+    ///Spread according to the teleport vector, being the same as uniform unless personalized.
+    Teleport,
+    ///Spread according to the given weights.
+    Weights(HashMap<&'a VertexKey, f64>),
+}
+
+type Weight<'a, EdgeKey> = Box<dyn 'a + Fn(&EdgeKey) -> f64>;
+
+///Configuration of the power iteration computing the PageRank of every vertex.
+pub struct PageRank<'a, Graph, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    graph: &'a Graph,
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    dangling: Dangling<'a, VertexKey>,
+    personalization: Option<HashMap<&'a VertexKey, f64>>,
+    weight: Option<Weight<'a, EdgeKey>>,
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> PageRank<'a, Graph, VertexKey, EdgeKey>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+            dangling: Dangling::Teleport,
+            personalization: None,
+            weight: None,
+        }
+    }
+
+    ///The probability of following an edge, rather than teleporting. Defaults to 0.85.
+    pub fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    ///The iteration stops once the total change in rank is below this times the number of vertices. Defaults to 1e-6.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    ///Defaults to 100.
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn dangling(mut self, dangling: Dangling<'a, VertexKey>) -> Self {
+        self.dangling = dangling;
+        self
+    }
+
+    ///Teleports according to the given weights, rather than uniformly. Vertices not present get a weight of zero.
+    pub fn personalized(mut self, personalization: HashMap<&'a VertexKey, f64>) -> Self {
+        self.personalization = Some(personalization);
+        self
+    }
+
+    ///Splits the rank of a vertex across its outgoing edges proportionally to the weights of the edges.
+    pub fn weighted<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetEdge<EdgeKey>,
+        Function: 'a + Fn(&<Graph as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let graph = self.graph;
+        self.weight = Some(Box::new(move |edge| {
+            graph.get_edge(edge).map_or(0.0, &function)
+        }));
+        self
+    }
+
+    pub fn rank(&self) -> HashMap<&'a VertexKey, f64> {
+        let keys: Vec<_> = self.graph.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let size = keys.len();
+        if size == 0 {
+            return HashMap::new();
+        }
+
+        let normalize = |weights: &HashMap<&'a VertexKey, f64>| {
+            let vector: Vec<_> = keys
+                .iter()
+                .map(|x| weights.get(x).cloned().unwrap_or(0.0))
+                .collect();
+            let total: f64 = vector.iter().sum();
+            if total > 0.0 {
+                vector.into_iter().map(|x| x / total).collect()
+            } else {
+                vec![1.0 / size as f64; size]
+            }
+        };
+        let uniform = vec![1.0 / size as f64; size];
+        let teleport: Vec<f64> = match &self.personalization {
+            Some(weights) => normalize(weights),
+            None => uniform.clone(),
+        };
+        let dangling: Vec<f64> = match &self.dangling {
+            Dangling::Uniform => uniform,
+            Dangling::Teleport => teleport.clone(),
+            Dangling::Weights(weights) => normalize(weights),
+        };
+
+        let outgoing: Vec<Vec<(usize, f64)>> = keys
+            .iter()
+            .map(|key| {
+                let edges: Vec<_> = self
+                    .graph
+                    .neighbours(key)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(edge, to)| {
+                        let weight = self.weight.as_ref().map_or(1.0, |x| x(edge));
+                        let to = *index.get(to)?;
+                        if weight > 0.0 {
+                            Some((to, weight))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                let total: f64 = edges.iter().map(|x| x.1).sum();
+                edges.into_iter().map(|(x, w)| (x, w / total)).collect()
+            })
+            .collect();
+
+        let mut rank = vec![1.0 / size as f64; size];
+        for _ in 0..self.max_iterations {
+            let lost: f64 = (0..size)
+                .filter(|&x| outgoing[x].is_empty())
+                .map(|x| rank[x])
+                .sum();
+
+            let mut next: Vec<_> = (0..size)
+                .map(|x| self.damping * lost * dangling[x] + (1.0 - self.damping) * teleport[x])
+                .collect();
+            for (from, edges) in outgoing.iter().enumerate() {
+                for (to, weight) in edges {
+                    next[*to] += self.damping * rank[from] * weight;
+                }
+            }
+
+            let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if change < size as f64 * self.tolerance {
+                break;
+            }
+        }
+
+        keys.into_iter().zip(rank).collect()
+    }
+}
+
+pub trait PageRanking<'a, VertexKey, EdgeKey>
+where
+    Self: Sized,
+    VertexKey: Eq + Hash,
+{
+    fn pagerank(&'a self) -> PageRank<'a, Self, VertexKey, EdgeKey>;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> PageRanking<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn pagerank(&'a self) -> PageRank<'a, Self, VertexKey, EdgeKey> {
+        PageRank::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn graph(
+        size: usize,
+        edges: &[(usize, usize, f64)],
+    ) -> Oriented<Simple<usize, (), usize, f64>, Directed> {
+        let mut graph = Simple::default().orient(Directed);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to, weight)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *weight)).unwrap();
+        }
+        graph
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn cycle() {
+        let graph = graph(4, &[(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0), (3, 0, 1.0)]);
+        let rank = graph.pagerank().rank();
+        assert!(rank.values().all(|x| close(*x, 0.25)));
+    }
+
+    #[test]
+    fn star() {
+        let graph = graph(4, &[(1, 0, 1.0), (2, 0, 1.0), (3, 0, 1.0)]);
+        let rank = graph.pagerank().tolerance(1e-10).rank();
+
+        assert!(close(rank.values().sum(), 1.0));
+        assert!((1..4).all(|x| rank[&0] > rank[&x]));
+        assert!(close(rank[&1], rank[&2]));
+    }
+
+    #[test]
+    fn personalized() {
+        let graph = graph(3, &[(0, 1, 1.0), (1, 0, 1.0), (1, 2, 1.0), (2, 1, 1.0)]);
+        let mut teleport = HashMap::new();
+        teleport.insert(&0, 1.0);
+
+        let rank = graph.pagerank().personalized(teleport).rank();
+        assert!(rank[&0] > rank[&2]);
+        assert!(close(rank.values().sum(), 1.0));
+    }
+
+    #[test]
+    fn dangling() {
+        let graph = graph(2, &[(0, 1, 1.0)]);
+        let mut weights = HashMap::new();
+        weights.insert(&1, 1.0);
+
+        let uniform = graph.pagerank().dangling(Dangling::Uniform).rank();
+        let kept = graph.pagerank().dangling(Dangling::Weights(weights)).rank();
+        assert!(close(uniform.values().sum(), 1.0));
+        assert!(kept[&1] > uniform[&1]);
+    }
+
+    #[test]
+    fn weighted() {
+        let graph = graph(3, &[(0, 1, 3.0), (0, 2, 1.0), (1, 0, 1.0), (2, 0, 1.0)]);
+        let unweighted = graph.pagerank().rank();
+        let weighted = graph.pagerank().weighted(|x| *x).rank();
+
+        assert!(close(unweighted[&1], unweighted[&2]));
+        assert!(weighted[&1] > weighted[&2]);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::dev::orientation::{Directed, Undirected};
+use crate::dev::{orientation, GetEdge, Neighbours, Vertices};
+use crate::extended::header::Header;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+type Weight<'a, EdgeKey> = Box<dyn 'a + Fn(&EdgeKey) -> f64>;
+
+///Totally ordered distance, allowing it to be used within a binary heap.
+#[derive(Copy, Clone, Debug)]
+struct Distance(f64);
+
+impl PartialEq for Distance {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Distance {}
+
+impl PartialOrd for Distance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Distance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+///The shortest paths from a single source, as needed by Brandes' algorithm.
+struct ShortestPaths<'a, EdgeKey> {
+    ///Reached vertices, in order of non-decreasing distance.
+    order: Vec<usize>,
+    predecessors: Vec<Vec<(usize, &'a EdgeKey)>>,
+    paths: Vec<f64>,
+    distance: Vec<Option<f64>>,
+}
+
+///Centrality measures based on the shortest paths between every pair of vertices.
+pub struct PathCentrality<'a, Graph, VertexKey, EdgeKey, Orientation> {
+    graph: &'a Graph,
+    normalized: bool,
+    weight: Option<Weight<'a, EdgeKey>>,
+    phantom: PhantomData<(VertexKey, Orientation)>,
+}
+
+impl<'a, Graph, VertexKey, EdgeKey, Orientation>
+    PathCentrality<'a, Graph, VertexKey, EdgeKey, Orientation>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Orientation: orientation::Orientation,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+{
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            normalized: false,
+            weight: None,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Scales the results by the number of vertex pairs, making them comparable across graphs.
+    pub fn normalized(mut self, normalized: bool) -> Self {
+        self.normalized = normalized;
+        self
+    }
+
+    ///Measures the length of paths by the weights of the edges, rather than by counting edges.
+    pub fn weighted<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetEdge<EdgeKey>,
+        Function: 'a + Fn(&<Graph as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let graph = self.graph;
+        self.weight = Some(Box::new(move |edge| {
+            graph.get_edge(edge).map_or(0.0, &function)
+        }));
+        self
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn adjacency(&self) -> (Vec<&'a VertexKey>, Vec<Vec<(usize, &'a EdgeKey, f64)>>) {
+        let keys: Vec<_> = self.graph.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let adjacent = keys
+            .iter()
+            .enumerate()
+            .map(|(from, key)| {
+                self.graph
+                    .neighbours(key)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(edge, to)| {
+                        let to = *index.get(to)?;
+                        let weight = self.weight.as_ref().map_or(1.0, |x| x(edge));
+                        if to == from {
+                            None
+                        } else {
+                            Some((to, edge, weight))
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        (keys, adjacent)
+    }
+
+    fn shortest_paths(
+        &self,
+        adjacent: &[Vec<(usize, &'a EdgeKey, f64)>],
+        source: usize,
+    ) -> ShortestPaths<'a, EdgeKey> {
+        let size = adjacent.len();
+        let mut paths = ShortestPaths {
+            order: Vec::new(),
+            predecessors: vec![Vec::new(); size],
+            paths: vec![0.0; size],
+            distance: vec![None; size],
+        };
+        paths.paths[source] = 1.0;
+
+        if self.weight.is_none() {
+            paths.distance[source] = Some(0.0);
+            let mut queue = VecDeque::new();
+            queue.push_back(source);
+            while let Some(from) = queue.pop_front() {
+                paths.order.push(from);
+                let next = paths.distance[from].unwrap() + 1.0;
+                for &(to, edge, _) in &adjacent[from] {
+                    if paths.distance[to].is_none() {
+                        paths.distance[to] = Some(next);
+                        queue.push_back(to);
+                    }
+                    if paths.distance[to] == Some(next) {
+                        paths.paths[to] += paths.paths[from];
+                        paths.predecessors[to].push((from, edge));
+                    }
+                }
+            }
+            return paths;
+        }
+
+        let mut seen = vec![None; size];
+        seen[source] = Some(0.0);
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse(Header(Distance(0.0), source)));
+        while let Some(Reverse(Header(Distance(distance), from))) = queue.pop() {
+            if paths.distance[from].is_some() {
+                continue;
+            }
+            paths.distance[from] = Some(distance);
+            paths.order.push(from);
+            for &(to, edge, weight) in &adjacent[from] {
+                if paths.distance[to].is_some() {
+                    continue;
+                }
+                let next = distance + weight;
+                match seen[to] {
+                    Some(current) if next > current => {}
+                    Some(current) if next == current => {
+                        paths.paths[to] += paths.paths[from];
+                        paths.predecessors[to].push((from, edge));
+                    }
+                    _ => {
+                        seen[to] = Some(next);
+                        paths.paths[to] = paths.paths[from];
+                        paths.predecessors[to] = vec![(from, edge)];
+                        queue.push(Reverse(Header(Distance(next), to)));
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    ///Brandes' algorithm, counting every ordered pair of vertices.
+    #[allow(clippy::type_complexity)]
+    fn brandes(&self) -> (HashMap<&'a VertexKey, f64>, HashMap<&'a EdgeKey, f64>) {
+        let (keys, adjacent) = self.adjacency();
+        let size = keys.len();
+        let mut vertices = vec![0.0; size];
+        let mut edges = HashMap::new();
+        for (from, neighbours) in adjacent.iter().enumerate() {
+            for (to, edge, _) in neighbours {
+                if from != *to {
+                    edges.insert(*edge, 0.0);
+                }
+            }
+        }
+
+        for source in 0..size {
+            let mut paths = self.shortest_paths(&adjacent, source);
+            let mut dependency = vec![0.0; size];
+            while let Some(to) = paths.order.pop() {
+                for &(from, edge) in &paths.predecessors[to] {
+                    let share = paths.paths[from] / paths.paths[to] * (1.0 + dependency[to]);
+                    *edges.get_mut(edge).unwrap() += share;
+                    dependency[from] += share;
+                }
+                if to != source {
+                    vertices[to] += dependency[to];
+                }
+            }
+        }
+
+        let n = size as f64;
+        if self.normalized {
+            let pairs = (n - 1.0) * (n - 2.0);
+            if pairs > 0.0 {
+                vertices.iter_mut().for_each(|x| *x /= pairs);
+            }
+            let pairs = n * (n - 1.0);
+            if pairs > 0.0 {
+                edges.values_mut().for_each(|x| *x /= pairs);
+            }
+        }
+        (keys.into_iter().zip(vertices).collect(), edges)
+    }
+
+    ///The inverse of the average distance to every reachable vertex, scaled by the fraction of vertices reachable when normalized.
+    pub fn closeness(&self) -> HashMap<&'a VertexKey, f64> {
+        let (keys, adjacent) = self.adjacency();
+        let size = keys.len();
+        (0..size)
+            .map(|source| {
+                let distance = self.shortest_paths(&adjacent, source).distance;
+                let reached = distance.iter().flatten().count() as f64 - 1.0;
+                let total: f64 = distance.iter().flatten().sum();
+                let closeness = if total > 0.0 {
+                    if self.normalized {
+                        reached / total * reached / (size as f64 - 1.0)
+                    } else {
+                        reached / total
+                    }
+                } else {
+                    0.0
+                };
+                (keys[source], closeness)
+            })
+            .collect()
+    }
+
+    ///The sum of the inverse distances to every other vertex, divided by the number of other vertices when normalized.
+    pub fn harmonic(&self) -> HashMap<&'a VertexKey, f64> {
+        let (keys, adjacent) = self.adjacency();
+        let size = keys.len();
+        (0..size)
+            .map(|source| {
+                let distance = self.shortest_paths(&adjacent, source).distance;
+                let mut harmonic: f64 = distance
+                    .iter()
+                    .flatten()
+                    .filter(|x| **x > 0.0)
+                    .map(|x| 1.0 / x)
+                    .sum();
+                if self.normalized && size > 1 {
+                    harmonic /= size as f64 - 1.0;
+                }
+                (keys[source], harmonic)
+            })
+            .collect()
+    }
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> PathCentrality<'a, Graph, VertexKey, EdgeKey, Directed>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    ///The fraction of shortest paths between other vertices, passing through each vertex.
+    pub fn betweenness(&self) -> HashMap<&'a VertexKey, f64> {
+        self.brandes().0
+    }
+
+    ///The fraction of shortest paths passing through each edge.
+    pub fn edge_betweenness(&self) -> HashMap<&'a EdgeKey, f64> {
+        self.brandes().1
+    }
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> PathCentrality<'a, Graph, VertexKey, EdgeKey, Undirected>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    ///The fraction of shortest paths between other vertices, passing through each vertex. Each unordered pair is counted once.
+    pub fn betweenness(&self) -> HashMap<&'a VertexKey, f64> {
+        let mut betweenness = self.brandes().0;
+        if !self.normalized {
+            betweenness.values_mut().for_each(|x| *x /= 2.0);
+        }
+        betweenness
+    }
+
+    ///The fraction of shortest paths passing through each edge. Each unordered pair is counted once.
+    pub fn edge_betweenness(&self) -> HashMap<&'a EdgeKey, f64> {
+        let mut betweenness = self.brandes().1;
+        if !self.normalized {
+            betweenness.values_mut().for_each(|x| *x /= 2.0);
+        }
+        betweenness
+    }
+}
+
+pub trait Centrality<'a, VertexKey, EdgeKey, Orientation>
+where
+    Self: Sized,
+{
+    fn centrality(&'a self) -> PathCentrality<'a, Self, VertexKey, EdgeKey, Orientation>;
+}
+
+impl<'a, Graph, VertexKey, EdgeKey, Orientation> Centrality<'a, VertexKey, EdgeKey, Orientation>
+    for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Orientation: orientation::Orientation,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn centrality(&'a self) -> PathCentrality<'a, Self, VertexKey, EdgeKey, Orientation> {
+        PathCentrality::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn path<O: orientation::Orientation>(
+        orientation: O,
+        size: usize,
+    ) -> Oriented<Simple<usize, (), usize, f64>, O>
+    where
+        Simple<usize, (), usize, f64>: AddEdge<O, usize, (usize, f64), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for i in 1..size {
+            graph.add_edge(&(i - 1), &i, (i - 1, 1.0)).unwrap();
+        }
+        graph
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn undirected_path() {
+        let graph = path(Undirected, 3);
+        let betweenness = graph.centrality().betweenness();
+        assert!(close(betweenness[&0], 0.0));
+        assert!(close(betweenness[&1], 1.0));
+
+        let normalized = graph.centrality().normalized(true).betweenness();
+        assert!(close(normalized[&1], 1.0));
+
+        let edges = graph.centrality().edge_betweenness();
+        assert!(close(edges[&0], 2.0));
+        assert!(close(edges[&1], 2.0));
+    }
+
+    #[test]
+    fn directed_path() {
+        let graph = path(Directed, 3);
+        let betweenness = graph.centrality().betweenness();
+        assert!(close(betweenness[&1], 1.0));
+
+        let edges = graph.centrality().edge_betweenness();
+        assert!(close(edges[&0], 2.0));
+    }
+
+    #[test]
+    fn parallel_paths() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        graph.add_edge(&0, &1, ("a", 1.0)).unwrap();
+        graph.add_edge(&0, &2, ("b", 1.0)).unwrap();
+        graph.add_edge(&1, &3, ("c", 1.0)).unwrap();
+        graph.add_edge(&2, &3, ("d", 5.0)).unwrap();
+
+        let unweighted = graph.centrality().betweenness();
+        assert!(close(unweighted[&1], 0.5));
+        assert!(close(unweighted[&2], 0.5));
+
+        let weighted = graph.centrality().weighted(|x| *x).betweenness();
+        assert!(close(weighted[&1], 2.0));
+        assert!(close(weighted[&2], 0.0));
+    }
+
+    #[test]
+    fn closeness() {
+        let graph = path(Undirected, 3);
+        let closeness = graph.centrality().closeness();
+        assert!(close(closeness[&1], 1.0));
+        assert!(close(closeness[&0], 2.0 / 3.0));
+
+        let normalized = graph.centrality().normalized(true).closeness();
+        assert!(close(normalized[&1], 1.0));
+
+        let directed = path(Directed, 3);
+        let closeness = directed.centrality().normalized(true).closeness();
+        assert!(close(closeness[&2], 0.0));
+        assert!(close(closeness[&1], 0.5));
+    }
+
+    #[test]
+    fn harmonic() {
+        let graph = path(Undirected, 3);
+        let harmonic = graph.centrality().harmonic();
+        assert!(close(harmonic[&0], 1.5));
+        assert!(close(harmonic[&1], 2.0));
+
+        let normalized = graph.centrality().normalized(true).harmonic();
+        assert!(close(normalized[&1], 1.0));
+    }
+}
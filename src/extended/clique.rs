@@ -1,76 +1,141 @@
-use crate::dev::orientation::{AddEdge, Orientation, Undirected};
-
-use crate::dev::{AddVertex, GetVertex, Neighbours, Vertices, orientation};
-use crate::extended::take_random;
-use crate::wrapper::sub::{intersection, SubGraph};
-use itertools::Itertools;
+use crate::dev::{orientation, Neighbours, Vertices};
+use crate::extended::degeneracy_ordering;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 use std::marker::PhantomData;
-use crate::dev::transform::transformers::VertexKey;
 
-fn maximal_clique<'a, G, O, T>(graph: &'a G, mut sub_set: HashSet<&'a T>) -> HashSet<&'a T>
+struct Frame<'a, VertexKey> {
+    clique: Vec<&'a VertexKey>,
+    candidates: HashSet<&'a VertexKey>,
+    excluded: HashSet<&'a VertexKey>,
+    remaining: Vec<&'a VertexKey>,
+}
+
+///Enumerates maximal cliques using the Bron–Kerbosch algorithm with pivoting, yielding each of them exactly once.
+pub struct CliqueIter<'a, Graph, VertexKey, Orientation> {
+    graph: &'a Graph,
+    adjacent: HashMap<&'a VertexKey, HashSet<&'a VertexKey>>,
+    order: VecDeque<&'a VertexKey>,
+    earlier: HashSet<&'a VertexKey>,
+    stack: Vec<Frame<'a, VertexKey>>,
+    phantom: PhantomData<(Orientation,)>,
+}
+
+impl<'a, Graph, VertexKey, Orientation> CliqueIter<'a, Graph, VertexKey, Orientation>
 where
-    T: 'a + Eq + Hash,
-    O: Orientation,
-    G: 'a + Neighbours<'a, O, T>,
+    VertexKey: 'a + Eq + Hash,
+    Orientation: orientation::Orientation,
+    Graph: 'a + Neighbours<'a, Orientation, VertexKey>,
 {
-    let mut common = sub_set
-        .iter()
-        .filter_map(|x| graph.neighbours(x))
-        .map(|x| x.into_iter().map(|x| x.1).collect::<HashSet<_>>())
-        .fold1(intersection)
-        .unwrap_or_default();
-
-    while let Some(from) = take_random(&mut common) {
-        sub_set.insert(from);
-        common = graph
-            .neighbours(from)
-            .into_iter()
-            .flatten()
-            .map(|x| x.1)
-            .filter(|x| common.contains(x))
-            .collect();
+    fn adjacent(&mut self, vertex: &'a VertexKey) -> &HashSet<&'a VertexKey> {
+        let graph = self.graph;
+        self.adjacent.entry(vertex).or_insert_with(|| {
+            graph
+                .neighbours(vertex)
+                .into_iter()
+                .flatten()
+                .map(|x| x.1)
+                .filter(|&x| x != vertex)
+                .collect()
+        })
     }
-    sub_set
-}
 
+    ///Pushes the next recursion level, or returns the clique when it cannot be extended any further.
+    fn descend(
+        &mut self,
+        clique: Vec<&'a VertexKey>,
+        candidates: HashSet<&'a VertexKey>,
+        excluded: HashSet<&'a VertexKey>,
+    ) -> Option<HashSet<&'a VertexKey>> {
+        if candidates.is_empty() {
+            return if excluded.is_empty() {
+                Some(clique.into_iter().collect())
+            } else {
+                None
+            };
+        }
 
+        let mut pivot = None;
+        let mut best = 0;
+        for &vertex in candidates.iter().chain(excluded.iter()) {
+            let count = self
+                .adjacent(vertex)
+                .iter()
+                .filter(|x| candidates.contains(*x))
+                .count();
+            if pivot.is_none() || count > best {
+                pivot = Some(vertex);
+                best = count;
+            }
+        }
+        let pivot = pivot.unwrap();
+        let remaining = candidates
+            .iter()
+            .filter(|x| !self.adjacent(pivot).contains(*x))
+            .cloned()
+            .collect::<Vec<_>>();
 
-pub struct CliqueIter<'a, Graph, VertexKey, Orientation> {
-    graph: &'a Graph,
-    queue: VecDeque<HashSet<&'a VertexKey>>,
-    visited: HashSet<HashSet<&'a VertexKey>>,
-    phantom : PhantomData<(Orientation,)>
+        self.stack.push(Frame {
+            clique,
+            candidates,
+            excluded,
+            remaining,
+        });
+        None
+    }
 }
 
 impl<'a, Graph, VertexKey, Orientation> Iterator for CliqueIter<'a, Graph, VertexKey, Orientation>
-    where
-        VertexKey: 'a + Eq + Hash,
-        Orientation: orientation::Orientation,
-        Graph: 'a + Neighbours<'a, Orientation, VertexKey>,
-        HashSet<&'a VertexKey> : Eq + Hash + Clone,{
+where
+    VertexKey: 'a + Eq + Hash,
+    Orientation: orientation::Orientation,
+    Graph: 'a + Neighbours<'a, Orientation, VertexKey>,
+{
     type Item = HashSet<&'a VertexKey>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut clique = maximal_clique(self.graph, self.queue.pop_front()?);
-        while !self.visited.insert(clique.clone()) {
-            clique = maximal_clique(self.graph, self.queue.pop_front()?);
-        }
-        let candidates:HashMap<_, HashSet<_>> = clique.iter()
-            .filter_map(|x| self.graph.neighbours(x).map(|y| (x, y)))
-            .fold(HashMap::new(),|mut state, (&from, neighbours)|{
-                for (_, i) in neighbours{
-                    state.entry(from).or_default().insert(i);
+        loop {
+            let (clique, candidates, excluded) = match self.stack.last_mut() {
+                Some(frame) => match frame.remaining.pop() {
+                    Some(vertex) => {
+                        frame.candidates.remove(vertex);
+                        let mut clique = frame.clique.clone();
+                        clique.push(vertex);
+                        let candidates = frame.candidates.clone();
+                        let excluded = frame.excluded.clone();
+                        frame.excluded.insert(vertex);
+
+                        let adjacent = self.adjacent(vertex);
+                        let candidates = candidates
+                            .into_iter()
+                            .filter(|x| adjacent.contains(x))
+                            .collect();
+                        let excluded = excluded
+                            .into_iter()
+                            .filter(|x| adjacent.contains(x))
+                            .collect();
+                        (clique, candidates, excluded)
+                    }
+                    None => {
+                        self.stack.pop();
+                        continue;
+                    }
+                },
+                None => {
+                    let vertex = self.order.pop_front()?;
+                    let adjacent = self.adjacent(vertex).clone();
+                    let earlier = &self.earlier;
+                    let (excluded, candidates) =
+                        adjacent.into_iter().partition(|x| earlier.contains(x));
+                    self.earlier.insert(vertex);
+                    (vec![vertex], candidates, excluded)
                 }
-                state
-            });
-        for (from, mut neighbours) in candidates{
-            neighbours.insert(from);
-            self.queue.push_back(neighbours);
+            };
+
+            if let Some(clique) = self.descend(clique, candidates, excluded) {
+                return Some(clique);
+            }
         }
-        clique.into()
-        
     }
 }
 
@@ -78,20 +143,125 @@ pub trait Clique<'a, VertexKey, Orientation>
 where
     Self: Sized,
 {
+    ///Every maximal clique containing the given seed vertex.
     fn clique(&'a self, seed: &'a VertexKey) -> CliqueIter<'a, Self, VertexKey, Orientation>;
+
+    ///Every maximal clique of the graph, visiting the vertices in degeneracy order.
+    fn cliques(&'a self) -> CliqueIter<'a, Self, VertexKey, Orientation>;
+
+    ///A clique of the largest possible size.
+    fn maximum_clique(&'a self) -> HashSet<&'a VertexKey>;
+
+    ///The size of the largest clique.
+    fn clique_number(&'a self) -> usize;
 }
 
 impl<'a, VertexKey, Orientation, Graph> Clique<'a, VertexKey, Orientation> for Graph
-    where
-        VertexKey : 'a + Eq + Hash{
+where
+    VertexKey: 'a + Eq + Hash,
+    Orientation: orientation::Orientation,
+    Graph: 'a + Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey>,
+{
     fn clique(&'a self, seed: &'a VertexKey) -> CliqueIter<'a, Self, VertexKey, Orientation> {
-        let mut set = HashSet::new();
-        set.insert(seed);
         CliqueIter {
             graph: self,
-            queue: vec![set].into(),
-            visited: Default::default(),
-            phantom: PhantomData
+            adjacent: Default::default(),
+            order: vec![seed].into(),
+            earlier: Default::default(),
+            stack: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn cliques(&'a self) -> CliqueIter<'a, Self, VertexKey, Orientation> {
+        let mut iter = CliqueIter {
+            graph: self,
+            adjacent: Default::default(),
+            order: Default::default(),
+            earlier: Default::default(),
+            stack: Vec::new(),
+            phantom: PhantomData,
+        };
+        for vertex in self.vertices() {
+            iter.adjacent(vertex);
         }
+        iter.order = degeneracy_ordering(&iter.adjacent).into();
+        iter
     }
-}
\ No newline at end of file
+
+    fn maximum_clique(&'a self) -> HashSet<&'a VertexKey> {
+        self.cliques().max_by_key(HashSet::len).unwrap_or_default()
+    }
+
+    fn clique_number(&'a self) -> usize {
+        self.maximum_clique().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::{AddEdge, Undirected};
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::Orient;
+
+    fn set(keys: &[i32]) -> HashSet<&i32> {
+        keys.iter().collect()
+    }
+
+    #[test]
+    fn two_triangles() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..5 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (a, b)) in [(0, 1), (1, 2), (2, 0), (1, 3), (2, 3)].iter().enumerate() {
+            graph.add_edge(a, b, (i, ())).unwrap();
+        }
+
+        let mut cliques: Vec<_> = graph.cliques().collect();
+        cliques.sort_by_key(|x| x.iter().map(|x| **x).sum::<i32>());
+
+        assert_eq!(cliques, vec![set(&[0, 1, 2]), set(&[4]), set(&[1, 2, 3])]);
+        assert_eq!(graph.clique_number(), 3);
+    }
+
+    #[test]
+    fn complete() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+            graph.add_edge(&i, &i, ((i, i), ())).unwrap();
+            for j in 0..i {
+                graph.add_edge(&i, &j, ((i, j), ())).unwrap();
+            }
+        }
+
+        assert_eq!(graph.cliques().count(), 1);
+        assert_eq!(graph.maximum_clique(), set(&[0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn seed() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (a, b)) in [(0, 1), (1, 2), (2, 0), (2, 3)].iter().enumerate() {
+            graph.add_edge(a, b, (i, ())).unwrap();
+        }
+
+        let cliques: Vec<_> = graph.clique(&0).collect();
+        assert_eq!(cliques, vec![set(&[0, 1, 2])]);
+        assert_eq!(graph.clique(&2).count(), 2);
+    }
+
+    #[test]
+    fn empty() {
+        let graph: Simple<i32, (), i32, ()> = Simple::default();
+        let graph = graph.orient(Undirected);
+        assert_eq!(graph.cliques().count(), 0);
+        assert_eq!(graph.clique_number(), 0);
+    }
+}
@@ -0,0 +1,312 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{Neighbours, Vertices};
+use crate::extended::{adjacency, degeneracy_ordering};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+///The order in which the greedy coloring visits the vertices.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Order {
+    ///Vertices of higher degree are colored first.
+    LargestFirst,
+    ///Reverse degeneracy order, such that each vertex has few colored neighbours when visited.
+    SmallestLast,
+    ///The vertex with the most distinctly colored neighbours is colored next, ties broken by degree.
+    DSatur,
+}
+
+///The first color not used by any of the given neighbours.
+fn smallest_free<'a, VertexKey>(
+    neighbours: &HashSet<&'a VertexKey>,
+    colors: &HashMap<&'a VertexKey, usize>,
+) -> usize
+where
+    VertexKey: Eq + Hash,
+{
+    let used: HashSet<_> = neighbours.iter().filter_map(|x| colors.get(x)).collect();
+    (0..).find(|x| !used.contains(x)).unwrap()
+}
+
+fn dsatur<'a, VertexKey>(
+    adjacent: &HashMap<&'a VertexKey, HashSet<&'a VertexKey>>,
+) -> HashMap<&'a VertexKey, usize>
+where
+    VertexKey: Eq + Hash,
+{
+    let mut colors = HashMap::new();
+    let mut saturation: HashMap<_, HashSet<usize>> =
+        adjacent.keys().map(|&x| (x, HashSet::new())).collect();
+
+    while let Some(vertex) = saturation
+        .iter()
+        .max_by_key(|(vertex, seen)| (seen.len(), adjacent[*vertex].len()))
+        .map(|(&vertex, _)| vertex)
+    {
+        saturation.remove(vertex);
+        let color = smallest_free(&adjacent[vertex], &colors);
+        colors.insert(vertex, color);
+        for neighbour in &adjacent[vertex] {
+            if let Some(seen) = saturation.get_mut(neighbour) {
+                seen.insert(color);
+            }
+        }
+    }
+    colors
+}
+
+///Attempts to color the vertices in the given order with at most `limit` colors.
+fn backtrack(
+    adjacent: &[Vec<usize>],
+    order: &[usize],
+    colors: &mut Vec<Option<usize>>,
+    limit: usize,
+    used: usize,
+) -> bool {
+    let vertex = match order.first() {
+        Some(&vertex) => vertex,
+        None => return true,
+    };
+    for color in 0..limit.min(used + 1) {
+        if adjacent[vertex].iter().all(|&x| colors[x] != Some(color)) {
+            colors[vertex] = Some(color);
+            if backtrack(adjacent, &order[1..], colors, limit, used.max(color + 1)) {
+                return true;
+            }
+        }
+    }
+    colors[vertex] = None;
+    false
+}
+
+///Vertex coloring of undirected graphs, assigning each vertex a color different from its neighbours. Self loops are ignored.
+pub trait Coloring<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///Colors the vertices one at a time in the given order, using the smallest color not taken by a neighbour.
+    fn greedy_coloring(&'a self, order: Order) -> HashMap<&'a VertexKey, usize>;
+
+    ///A coloring using as few colors as possible, found by backtracking. Only feasible for small graphs.
+    fn optimal_coloring(&'a self) -> HashMap<&'a VertexKey, usize>;
+
+    ///The smallest number of colors needed to color the graph.
+    fn chromatic_number(&'a self) -> usize;
+
+    ///Splits the graph into two sides without any edges within them, or returns an odd cycle proving that this is impossible.
+    #[allow(clippy::type_complexity)]
+    fn bipartite(
+        &'a self,
+    ) -> Result<(HashSet<&'a VertexKey>, HashSet<&'a VertexKey>), Vec<(&'a VertexKey, &'a EdgeKey)>>;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> Coloring<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn greedy_coloring(&'a self, order: Order) -> HashMap<&'a VertexKey, usize> {
+        let adjacent = adjacency::<_, Undirected, _>(self);
+        let vertices = match order {
+            Order::LargestFirst => {
+                let mut vertices: Vec<_> = adjacent.keys().cloned().collect();
+                vertices.sort_by_key(|x| std::cmp::Reverse(adjacent[x].len()));
+                vertices
+            }
+            Order::SmallestLast => {
+                let mut vertices = degeneracy_ordering(&adjacent);
+                vertices.reverse();
+                vertices
+            }
+            Order::DSatur => return dsatur(&adjacent),
+        };
+
+        let mut colors = HashMap::new();
+        for vertex in vertices {
+            let color = smallest_free(&adjacent[vertex], &colors);
+            colors.insert(vertex, color);
+        }
+        colors
+    }
+
+    fn optimal_coloring(&'a self) -> HashMap<&'a VertexKey, usize> {
+        let adjacent = adjacency::<_, Undirected, _>(self);
+        let greedy = dsatur(&adjacent);
+        let upper = greedy.values().max().map_or(0, |x| x + 1);
+
+        let mut keys: Vec<_> = adjacent.keys().cloned().collect();
+        keys.sort_by_key(|x| std::cmp::Reverse(adjacent[x].len()));
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let indexed: Vec<Vec<_>> = keys
+            .iter()
+            .map(|x| adjacent[x].iter().map(|y| index[y]).collect())
+            .collect();
+        let order: Vec<_> = (0..keys.len()).collect();
+
+        for limit in 1..upper {
+            let mut colors = vec![None; keys.len()];
+            if backtrack(&indexed, &order, &mut colors, limit, 0) {
+                return keys.into_iter().zip(colors.into_iter().flatten()).collect();
+            }
+        }
+        greedy
+    }
+
+    fn chromatic_number(&'a self) -> usize {
+        self.optimal_coloring().values().max().map_or(0, |x| x + 1)
+    }
+
+    fn bipartite(
+        &'a self,
+    ) -> Result<(HashSet<&'a VertexKey>, HashSet<&'a VertexKey>), Vec<(&'a VertexKey, &'a EdgeKey)>>
+    {
+        let mut side = HashMap::new();
+        let mut parent: HashMap<&'a VertexKey, (&'a VertexKey, &'a EdgeKey)> = HashMap::new();
+
+        for root in self.vertices() {
+            if side.contains_key(root) {
+                continue;
+            }
+            side.insert(root, false);
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+
+            while let Some(from) = queue.pop_front() {
+                for (edge, to) in self.neighbours(from).into_iter().flatten() {
+                    match side.get(to) {
+                        None => {
+                            side.insert(to, !side[from]);
+                            parent.insert(to, (from, edge));
+                            queue.push_back(to);
+                        }
+                        Some(color) if *color == side[from] => {
+                            return Err(odd_cycle(&parent, from, edge, to));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut sides = (HashSet::new(), HashSet::new());
+        for (vertex, right) in side {
+            if right {
+                sides.1.insert(vertex);
+            } else {
+                sides.0.insert(vertex);
+            }
+        }
+        Ok(sides)
+    }
+}
+
+///Joins the breadth first search paths of two equally deep vertices, along with the edge between them, into a cycle.
+fn odd_cycle<'a, VertexKey, EdgeKey>(
+    parent: &HashMap<&'a VertexKey, (&'a VertexKey, &'a EdgeKey)>,
+    from: &'a VertexKey,
+    edge: &'a EdgeKey,
+    to: &'a VertexKey,
+) -> Vec<(&'a VertexKey, &'a EdgeKey)>
+where
+    VertexKey: Eq + Hash,
+{
+    let mut down = Vec::new();
+    let mut up = vec![(from, edge)];
+    let (mut a, mut b) = (from, to);
+    while a != b {
+        let (pa, ea) = parent[a];
+        let (pb, eb) = parent[b];
+        down.push((pa, ea));
+        up.push((b, eb));
+        a = pa;
+        b = pb;
+    }
+    down.reverse();
+    down.extend(up);
+    down
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn cycle(length: usize) -> Oriented<Simple<usize, (), usize, ()>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..length {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for i in 0..length {
+            graph.add_edge(&i, &((i + 1) % length), (i, ())).unwrap();
+        }
+        graph
+    }
+
+    fn proper<'a>(
+        graph: &'a Oriented<Simple<usize, (), usize, ()>, Undirected>,
+        colors: &HashMap<&'a usize, usize>,
+    ) -> bool {
+        graph
+            .edges
+            .values()
+            .all(|x| colors[&x.from] != colors[&x.to])
+    }
+
+    #[test]
+    fn greedy() {
+        let graph = cycle(7);
+        for order in [Order::LargestFirst, Order::SmallestLast, Order::DSatur].iter() {
+            let colors = graph.greedy_coloring(*order);
+            assert_eq!(colors.len(), 7);
+            assert!(proper(&graph, &colors));
+        }
+    }
+
+    #[test]
+    fn chromatic_number() {
+        assert_eq!(cycle(6).chromatic_number(), 2);
+        assert_eq!(cycle(7).chromatic_number(), 3);
+
+        let mut wheel = cycle(5);
+        wheel.add_vertex((5, ())).unwrap();
+        for i in 0..5 {
+            wheel.add_edge(&5, &i, (i + 5, ())).unwrap();
+        }
+        assert_eq!(wheel.chromatic_number(), 4);
+        assert!(proper(&wheel, &wheel.optimal_coloring()));
+    }
+
+    #[test]
+    fn bipartite() {
+        let graph = cycle(4);
+        let (left, right) = graph.bipartite().unwrap();
+        assert_eq!(left.len(), 2);
+        assert_eq!(right.len(), 2);
+    }
+
+    #[test]
+    fn odd_cycle_witness() {
+        let graph = cycle(5);
+        let witness = graph.bipartite().unwrap_err();
+        assert_eq!(witness.len(), 5);
+
+        for (i, (from, edge)) in witness.iter().enumerate() {
+            let (to, _) = witness[(i + 1) % witness.len()];
+            let node = &graph.edges[edge];
+            assert_eq!(node.other(from), to);
+        }
+    }
+
+    #[test]
+    fn self_loop_witness() {
+        let mut graph = Simple::default().orient(Undirected);
+        graph.add_vertex((0, ())).unwrap();
+        graph.add_edge(&0, &0, ("loop", ())).unwrap();
+
+        assert_eq!(graph.bipartite(), Err(vec![(&0, &"loop")]));
+    }
+}
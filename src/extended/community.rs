@@ -0,0 +1,393 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{GetEdge, Neighbours, Vertices};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+type Weight<'a, EdgeKey> = Box<dyn 'a + Fn(&EdgeKey) -> f64>;
+
+///The community of every vertex, along with the modularity it achieves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Partition<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    pub communities: HashMap<&'a VertexKey, usize>,
+    pub modularity: f64,
+}
+
+///Weighted adjacency between indexed vertices. A self loop is counted twice, once for each end.
+type Adjacency = Vec<HashMap<usize, f64>>;
+
+fn degrees(adjacent: &[HashMap<usize, f64>]) -> Vec<f64> {
+    adjacent.iter().map(|x| x.values().sum()).collect()
+}
+
+fn modularity_of(adjacent: &[HashMap<usize, f64>], community: &[usize], resolution: f64) -> f64 {
+    let degree = degrees(adjacent);
+    let total: f64 = degree.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+
+    let mut internal = HashMap::new();
+    let mut incident = HashMap::new();
+    for (from, neighbours) in adjacent.iter().enumerate() {
+        *incident.entry(community[from]).or_insert(0.0) += degree[from];
+        for (to, weight) in neighbours {
+            if community[from] == community[*to] {
+                *internal.entry(community[from]).or_insert(0.0) += weight;
+            }
+        }
+    }
+    incident
+        .iter()
+        .map(|(c, incident)| {
+            internal.get(c).unwrap_or(&0.0) / total - resolution * (incident / total).powi(2)
+        })
+        .sum()
+}
+
+///Renumbers the communities from zero, in order of first appearance.
+fn relabel(community: &mut [usize]) -> usize {
+    let mut labels = HashMap::new();
+    for c in community.iter_mut() {
+        let next = labels.len();
+        *c = *labels.entry(*c).or_insert(next);
+    }
+    labels.len()
+}
+
+///Community detection on undirected graphs, where edges are dense within communities and sparse between them.
+pub struct CommunityDetection<'a, Graph, VertexKey, EdgeKey> {
+    graph: &'a Graph,
+    resolution: f64,
+    weight: Option<Weight<'a, EdgeKey>>,
+    phantom: PhantomData<VertexKey>,
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> CommunityDetection<'a, Graph, VertexKey, EdgeKey>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            resolution: 1.0,
+            weight: None,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Values above one favour smaller communities, values below favour larger ones. Defaults to 1.
+    pub fn resolution(mut self, resolution: f64) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    ///Uses the weights of the edges, rather than counting every edge as one.
+    pub fn weighted<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetEdge<EdgeKey>,
+        Function: 'a + Fn(&<Graph as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let graph = self.graph;
+        self.weight = Some(Box::new(move |edge| {
+            graph.get_edge(edge).map_or(0.0, &function)
+        }));
+        self
+    }
+
+    fn adjacency(&self) -> (Vec<&'a VertexKey>, Adjacency) {
+        let keys: Vec<_> = self.graph.vertices().into_iter().collect();
+        let adjacent = self.adjacency_of(&keys);
+        (keys, adjacent)
+    }
+
+    ///The summed edge weights between the given vertices, indexed by their position.
+    fn adjacency_of(&self, keys: &[&'a VertexKey]) -> Adjacency {
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        keys.iter()
+            .map(|key| {
+                let mut neighbours = HashMap::new();
+                for (edge, to) in self.graph.neighbours(key).into_iter().flatten() {
+                    if let Some(&to) = index.get(to) {
+                        let weight = self.weight.as_ref().map_or(1.0, |x| x(edge));
+                        *neighbours.entry(to).or_insert(0.0) += weight;
+                    }
+                }
+                neighbours
+            })
+            .collect()
+    }
+
+    fn partition(
+        &self,
+        keys: Vec<&'a VertexKey>,
+        community: Vec<usize>,
+        modularity: f64,
+    ) -> Partition<'a, VertexKey> {
+        Partition {
+            communities: keys.into_iter().zip(community).collect(),
+            modularity,
+        }
+    }
+
+    ///The modularity of the given partition. Vertices not present are placed in communities of their own.
+    pub fn modularity(&self, partition: &HashMap<&'a VertexKey, usize>) -> f64 {
+        let (keys, adjacent) = self.adjacency();
+        let offset = partition.values().max().map_or(0, |x| x + 1);
+        let community: Vec<_> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| partition.get(key).cloned().unwrap_or(offset + i))
+            .collect();
+        modularity_of(&adjacent, &community, self.resolution)
+    }
+
+    ///Moves single vertices between communities while that increases modularity, then merges every community into a single vertex and repeats.
+    pub fn louvain(&self) -> Partition<'a, VertexKey> {
+        let (keys, adjacent) = self.adjacency();
+        let mut community: Vec<_> = (0..keys.len()).collect();
+        let mut current = adjacent.clone();
+
+        loop {
+            let (mut moved, changed) = self.local_moving(&current);
+            if !changed {
+                break;
+            }
+            let size = relabel(&mut moved);
+            for c in community.iter_mut() {
+                *c = moved[*c];
+            }
+
+            let mut aggregated = vec![HashMap::new(); size];
+            for (from, neighbours) in current.iter().enumerate() {
+                for (to, weight) in neighbours {
+                    *aggregated[moved[from]].entry(moved[*to]).or_insert(0.0) += weight;
+                }
+            }
+            current = aggregated;
+        }
+
+        relabel(&mut community);
+        let modularity = modularity_of(&adjacent, &community, self.resolution);
+        self.partition(keys, community, modularity)
+    }
+
+    fn local_moving(&self, adjacent: &[HashMap<usize, f64>]) -> (Vec<usize>, bool) {
+        let degree = degrees(adjacent);
+        let total: f64 = degree.iter().sum();
+        let mut community: Vec<_> = (0..adjacent.len()).collect();
+        let mut incident = degree.clone();
+        let mut changed = false;
+        if total <= 0.0 {
+            return (community, changed);
+        }
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for vertex in 0..adjacent.len() {
+                let old = community[vertex];
+                let mut links = HashMap::new();
+                links.insert(old, 0.0);
+                for (to, weight) in &adjacent[vertex] {
+                    if *to != vertex {
+                        *links.entry(community[*to]).or_insert(0.0) += weight;
+                    }
+                }
+
+                incident[old] -= degree[vertex];
+                let gain =
+                    |c: usize| links[&c] - self.resolution * incident[c] * degree[vertex] / total;
+                let mut candidates: Vec<_> = links.keys().cloned().collect();
+                candidates.sort_unstable();
+                let mut best = old;
+                for c in candidates {
+                    if gain(c) > gain(best) + 1e-12 {
+                        best = c;
+                    }
+                }
+                incident[best] += degree[vertex];
+
+                if best != old {
+                    community[vertex] = best;
+                    improved = true;
+                    changed = true;
+                }
+            }
+        }
+        (community, changed)
+    }
+
+    ///Repeatedly gives every vertex, in random order, the label carrying the most weight among its neighbours, until no label changes.
+    ///The vertices are sorted first, such that the same seed gives the same partition for equal graphs.
+    pub fn label_propagation<R>(&self, rng: &mut R) -> Partition<'a, VertexKey>
+    where
+        VertexKey: Ord,
+        R: Rng,
+    {
+        let mut keys: Vec<_> = self.graph.vertices().into_iter().collect();
+        keys.sort();
+        let adjacent = self.adjacency_of(&keys);
+        let mut label: Vec<_> = (0..keys.len()).collect();
+        let mut order: Vec<_> = (0..keys.len()).collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            order.shuffle(rng);
+            for &vertex in &order {
+                let mut neighbours: Vec<_> = adjacent[vertex].iter().collect();
+                neighbours.sort_unstable_by_key(|x| x.0);
+                let mut weights = HashMap::new();
+                for (to, weight) in neighbours {
+                    if *to != vertex {
+                        *weights.entry(label[*to]).or_insert(0.0) += weight;
+                    }
+                }
+                let heaviest = weights.values().cloned().fold(0.0, f64::max);
+                if heaviest <= 0.0 {
+                    continue;
+                }
+                let mut best: Vec<_> = weights
+                    .into_iter()
+                    .filter(|x| x.1 == heaviest)
+                    .map(|x| x.0)
+                    .collect();
+                if best.contains(&label[vertex]) {
+                    continue;
+                }
+                best.sort_unstable();
+                label[vertex] = *best.choose(rng).unwrap();
+                changed = true;
+            }
+        }
+
+        relabel(&mut label);
+        let modularity = modularity_of(&adjacent, &label, self.resolution);
+        self.partition(keys, label, modularity)
+    }
+}
+
+pub trait Communities<'a, VertexKey, EdgeKey>
+where
+    Self: Sized,
+{
+    fn communities(&'a self) -> CommunityDetection<'a, Self, VertexKey, EdgeKey>;
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> Communities<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn communities(&'a self) -> CommunityDetection<'a, Self, VertexKey, EdgeKey> {
+        CommunityDetection::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    ///Two triangles joined by a single edge between vertices 2 and 3.
+    fn barbell(bridge: f64) -> Oriented<Simple<usize, (), usize, f64>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..6 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        let edges = [(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5)];
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, 1.0)).unwrap();
+        }
+        graph.add_edge(&2, &3, (6, bridge)).unwrap();
+        graph
+    }
+
+    fn split(partition: &HashMap<&usize, usize>) -> bool {
+        partition[&0] == partition[&1]
+            && partition[&1] == partition[&2]
+            && partition[&3] == partition[&4]
+            && partition[&4] == partition[&5]
+            && partition[&0] != partition[&3]
+    }
+
+    #[test]
+    fn modularity() {
+        let graph = barbell(1.0);
+        let mut partition = HashMap::new();
+        for i in [0, 1, 2].iter() {
+            partition.insert(i, 0);
+        }
+        for i in [3, 4, 5].iter() {
+            partition.insert(i, 1);
+        }
+        let expected = 2.0 * (6.0 / 14.0 - 0.25);
+        assert!((graph.communities().modularity(&partition) - expected).abs() < 1e-9);
+
+        let single: HashMap<_, _> = partition.keys().map(|&x| (x, 0)).collect();
+        assert!(graph.communities().modularity(&single).abs() < 1e-9);
+    }
+
+    #[test]
+    fn louvain() {
+        let graph = barbell(1.0);
+        let partition = graph.communities().louvain();
+        assert!(split(&partition.communities));
+        assert!((partition.modularity - 2.0 * (6.0 / 14.0 - 0.25)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn weighted() {
+        let graph = barbell(10.0);
+        let partition = graph.communities().weighted(|x| *x).louvain();
+        assert!(!split(&partition.communities));
+        assert_eq!(partition.communities[&2], partition.communities[&3]);
+    }
+
+    #[test]
+    fn label_propagation() {
+        let graph = barbell(1.0);
+        let first = graph
+            .communities()
+            .label_propagation(&mut StdRng::seed_from_u64(7));
+        let second = graph
+            .communities()
+            .label_propagation(&mut StdRng::seed_from_u64(7));
+        assert_eq!(first, second);
+
+        //An identical graph built separately, whose vertices are stored in another order.
+        let other = barbell(1.0);
+        let third = other
+            .communities()
+            .label_propagation(&mut StdRng::seed_from_u64(7));
+        let labels = |partition: &Partition<usize>| {
+            let mut labels: Vec<_> = partition
+                .communities
+                .iter()
+                .map(|(&&key, &label)| (key, label))
+                .collect();
+            labels.sort_unstable();
+            labels
+        };
+        assert_eq!(labels(&first), labels(&third));
+
+        let modularity = graph.communities().modularity(&first.communities);
+        assert!((first.modularity - modularity).abs() < 1e-9);
+    }
+}
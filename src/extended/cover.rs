@@ -0,0 +1,346 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{Neighbours, Vertices};
+use crate::extended::adjacency;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///The most vertices a minimum vertex cover is searched for on, as branch and bound takes exponential time.
+pub const MINIMUM_COVER_LIMIT: usize = 64;
+
+///Vertex covers, independent sets and dominating sets of undirected graphs.
+///A vertex with a self loop is part of every vertex cover, and thus never part of an independent set.
+pub trait Cover<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///A vertex cover at most twice the minimum size, taking both ends of the edges of a maximal matching.
+    fn approximate_vertex_cover(&'a self) -> HashSet<&'a VertexKey>;
+
+    ///A vertex cover of minimum size, found using branch and bound. Takes exponential time,
+    ///so None for graphs of more than MINIMUM_COVER_LIMIT vertices.
+    fn vertex_cover(&'a self) -> Option<HashSet<&'a VertexKey>>;
+
+    ///A maximal independent set, repeatedly taking a vertex of smallest remaining degree and removing its neighbours.
+    fn greedy_independent_set(&'a self) -> HashSet<&'a VertexKey>;
+
+    ///An independent set of maximum size, being the complement of a minimum vertex cover. Takes exponential time,
+    ///so None for graphs of more than MINIMUM_COVER_LIMIT vertices.
+    fn maximum_independent_set(&'a self) -> Option<HashSet<&'a VertexKey>>;
+
+    ///A dominating set, repeatedly taking the vertex which dominates the most vertices yet to be dominated.
+    ///Its size is within a logarithmic factor of the minimum.
+    fn dominating_set(&'a self) -> HashSet<&'a VertexKey>;
+}
+
+///The vertices numbered from 0, along with their neighbours and whether they have a self loop.
+struct DenseGraph<'a, VertexKey> {
+    keys: Vec<&'a VertexKey>,
+    adjacent: Vec<Vec<usize>>,
+    loops: Vec<bool>,
+}
+
+impl<'a, VertexKey> DenseGraph<'a, VertexKey>
+where
+    VertexKey: 'a + Eq + Hash,
+{
+    fn new<Graph>(graph: &'a Graph) -> Self
+    where
+        Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey>,
+    {
+        let adjacent = adjacency::<_, Undirected, _>(graph);
+        let keys: Vec<_> = adjacent.keys().cloned().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let loops = keys
+            .iter()
+            .map(|&key| {
+                graph
+                    .neighbours(key)
+                    .into_iter()
+                    .flatten()
+                    .any(|x| x.1 == key)
+            })
+            .collect();
+        let adjacent = keys
+            .iter()
+            .map(|x| adjacent[x].iter().map(|y| index[y]).collect())
+            .collect();
+        Self {
+            keys,
+            adjacent,
+            loops,
+        }
+    }
+
+    fn keys<Indices>(&self, indices: Indices) -> HashSet<&'a VertexKey>
+    where
+        Indices: IntoIterator<Item = usize>,
+    {
+        indices.into_iter().map(|x| self.keys[x]).collect()
+    }
+
+    fn approximate_cover(&self) -> Vec<bool> {
+        let mut cover = self.loops.clone();
+        for (from, neighbours) in self.adjacent.iter().enumerate() {
+            for &to in neighbours {
+                if !cover[from] && !cover[to] {
+                    cover[from] = true;
+                    cover[to] = true;
+                }
+            }
+        }
+        cover
+    }
+
+    fn minimum_cover(&self) -> Option<Vec<bool>> {
+        if self.keys.len() > MINIMUM_COVER_LIMIT {
+            return None;
+        }
+        let mut removed = self.loops.clone();
+        let mut cover: Vec<_> = (0..self.keys.len()).filter(|&x| removed[x]).collect();
+        let approximate = self.approximate_cover();
+        let mut best: Vec<_> = (0..self.keys.len()).filter(|&x| approximate[x]).collect();
+        self.branch(&mut removed, &mut cover, &mut best);
+
+        let mut output = vec![false; self.keys.len()];
+        for x in best {
+            output[x] = true;
+        }
+        Some(output)
+    }
+
+    ///Either the vertex of largest degree is in the cover, or all of its neighbours are.
+    ///Branches are cut once the remaining edges cannot be covered by fewer vertices than the best cover found.
+    fn branch(&self, removed: &mut Vec<bool>, cover: &mut Vec<usize>, best: &mut Vec<usize>) {
+        let degree = |x: usize| self.adjacent[x].iter().filter(|&&y| !removed[y]).count();
+        let degrees: Vec<_> = (0..self.keys.len())
+            .map(|x| if removed[x] { 0 } else { degree(x) })
+            .collect();
+        let (vertex, &largest) = match degrees.iter().enumerate().max_by_key(|x| x.1) {
+            Some(max) => max,
+            None => return,
+        };
+        if largest == 0 {
+            if cover.len() < best.len() {
+                *best = cover.clone();
+            }
+            return;
+        }
+        let edges = degrees.iter().sum::<usize>() / 2;
+        if cover.len() + edges.div_ceil(largest) >= best.len() {
+            return;
+        }
+
+        removed[vertex] = true;
+        cover.push(vertex);
+        self.branch(removed, cover, best);
+        cover.pop();
+
+        let neighbours: Vec<_> = self.adjacent[vertex]
+            .iter()
+            .cloned()
+            .filter(|&x| !removed[x])
+            .collect();
+        for &x in &neighbours {
+            removed[x] = true;
+            cover.push(x);
+        }
+        self.branch(removed, cover, best);
+        for &x in &neighbours {
+            removed[x] = false;
+            cover.pop();
+        }
+        removed[vertex] = false;
+    }
+}
+
+impl<'a, VertexKey, Graph> Cover<'a, VertexKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey>,
+{
+    fn approximate_vertex_cover(&'a self) -> HashSet<&'a VertexKey> {
+        let dense = DenseGraph::new(self);
+        let cover = dense.approximate_cover();
+        dense.keys((0..cover.len()).filter(|&x| cover[x]))
+    }
+
+    fn vertex_cover(&'a self) -> Option<HashSet<&'a VertexKey>> {
+        let dense = DenseGraph::new(self);
+        let cover = dense.minimum_cover()?;
+        Some(dense.keys((0..cover.len()).filter(|&x| cover[x])))
+    }
+
+    fn greedy_independent_set(&'a self) -> HashSet<&'a VertexKey> {
+        let dense = DenseGraph::new(self);
+        let mut removed = dense.loops.clone();
+        let mut independent = Vec::new();
+        loop {
+            let next = (0..removed.len())
+                .filter(|&x| !removed[x])
+                .min_by_key(|&x| dense.adjacent[x].iter().filter(|&&y| !removed[y]).count());
+            let vertex = match next {
+                Some(vertex) => vertex,
+                None => break,
+            };
+            removed[vertex] = true;
+            for &x in &dense.adjacent[vertex] {
+                removed[x] = true;
+            }
+            independent.push(vertex);
+        }
+        dense.keys(independent)
+    }
+
+    fn maximum_independent_set(&'a self) -> Option<HashSet<&'a VertexKey>> {
+        let dense = DenseGraph::new(self);
+        let cover = dense.minimum_cover()?;
+        Some(dense.keys((0..cover.len()).filter(|&x| !cover[x])))
+    }
+
+    fn dominating_set(&'a self) -> HashSet<&'a VertexKey> {
+        let dense = DenseGraph::new(self);
+        let mut dominated = vec![false; dense.keys.len()];
+        let mut dominating = Vec::new();
+        let gain = |dominated: &[bool], x: usize| {
+            dense.adjacent[x]
+                .iter()
+                .chain(Some(&x))
+                .filter(|&&y| !dominated[y])
+                .count()
+        };
+        while let Some(vertex) = (0..dominated.len()).max_by_key(|&x| gain(&dominated, x)) {
+            if gain(&dominated, vertex) == 0 {
+                break;
+            }
+            dominated[vertex] = true;
+            for &x in &dense.adjacent[vertex] {
+                dominated[x] = true;
+            }
+            dominating.push(vertex);
+        }
+        dense.keys(dominating)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+    use crate::wrapper::sub::SubGraph;
+
+    fn graph(
+        size: usize,
+        edges: &[(usize, usize)],
+    ) -> Oriented<Simple<usize, (), usize, ()>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    fn covers(edges: &[(usize, usize)], cover: &HashSet<&usize>) -> bool {
+        edges
+            .iter()
+            .all(|(from, to)| cover.contains(from) || cover.contains(to))
+    }
+
+    ///The Petersen graph, with a minimum vertex cover of six vertices.
+    const PETERSEN: [(usize, usize); 15] = [
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 0),
+        (0, 5),
+        (1, 6),
+        (2, 7),
+        (3, 8),
+        (4, 9),
+        (5, 7),
+        (7, 9),
+        (9, 6),
+        (6, 8),
+        (8, 5),
+    ];
+
+    #[test]
+    fn vertex_cover() {
+        let petersen = graph(10, &PETERSEN);
+        let approximate = petersen.approximate_vertex_cover();
+        assert!(covers(&PETERSEN, &approximate));
+        assert!(approximate.len() <= 12);
+
+        let exact = petersen.vertex_cover().unwrap();
+        assert!(covers(&PETERSEN, &exact));
+        assert_eq!(exact.len(), 6);
+    }
+
+    #[test]
+    fn independent_set() {
+        let petersen = graph(10, &PETERSEN);
+        let independent = |set: &HashSet<&usize>| {
+            PETERSEN
+                .iter()
+                .all(|(from, to)| !(set.contains(from) && set.contains(to)))
+        };
+
+        let greedy = petersen.greedy_independent_set();
+        assert!(independent(&greedy));
+        assert!(greedy.len() >= 3);
+
+        let maximum = petersen.maximum_independent_set().unwrap();
+        assert!(independent(&maximum));
+        assert_eq!(maximum.len(), 4);
+
+        //The self loop on 0 keeps it out of every independent set.
+        let looped = graph(3, &[(0, 0), (0, 1)]);
+        let maximum = looped.maximum_independent_set().unwrap();
+        assert_eq!(maximum, [1, 2].iter().collect());
+        assert!(looped.vertex_cover().unwrap().contains(&0));
+    }
+
+    #[test]
+    fn limit() {
+        let size = MINIMUM_COVER_LIMIT + 1;
+        let edges: Vec<_> = (0..size).map(|x| (x, (x + 1) % size)).collect();
+        let cycle = graph(size, &edges);
+        assert_eq!(cycle.vertex_cover(), None);
+        assert_eq!(cycle.maximum_independent_set(), None);
+        assert!(covers(&edges, &cycle.approximate_vertex_cover()));
+
+        let spokes: Vec<_> = (1..MINIMUM_COVER_LIMIT).map(|x| (0, x)).collect();
+        let star = graph(MINIMUM_COVER_LIMIT, &spokes);
+        assert_eq!(star.vertex_cover(), Some([0].iter().collect()));
+    }
+
+    #[test]
+    fn dominating_set() {
+        //Two stars joined at their centres, dominated by the centres alone.
+        let stars = graph(8, &[(0, 1), (0, 2), (0, 3), (0, 4), (4, 5), (4, 6), (4, 7)]);
+        assert_eq!(stars.dominating_set(), [0, 4].iter().collect());
+
+        let isolated = graph(2, &[]);
+        assert_eq!(isolated.dominating_set().len(), 2);
+    }
+
+    #[test]
+    fn sub_graph() {
+        let petersen = graph(10, &PETERSEN);
+        let mut sub: SubGraph<_, Simple<_, _, usize, ()>> = SubGraph::new(&petersen);
+        let keys = [0, 1, 2, 3, 4];
+        for key in &keys {
+            sub.add_vertex(key).unwrap();
+        }
+        //The outer five cycle.
+        assert_eq!(sub.vertex_cover().unwrap().len(), 3);
+        assert_eq!(sub.maximum_independent_set().unwrap().len(), 2);
+        assert_eq!(sub.dominating_set().len(), 2);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{GetEdge, Neighbours, Vertices};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///A split of the vertices into two non empty sides, along with the total weight of the edges crossing it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cut<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    pub weight: f64,
+    pub partition: (HashSet<&'a VertexKey>, HashSet<&'a VertexKey>),
+}
+
+///Global minimum cut of undirected graphs, found using the Stoer-Wagner algorithm.
+pub trait MinimumCut<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///The cut of smallest weight, measuring each edge with the given function. Self loops are ignored, and None is returned for graphs with less than two vertices.
+    fn minimum_cut<Function>(&'a self, weight: Function) -> Option<Cut<'a, VertexKey>>
+    where
+        Self: GetEdge<EdgeKey>,
+        Function: Fn(&<Self as GetEdge<EdgeKey>>::Output) -> f64;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> MinimumCut<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn minimum_cut<Function>(&'a self, weight: Function) -> Option<Cut<'a, VertexKey>>
+    where
+        Self: GetEdge<EdgeKey>,
+        Function: Fn(&<Self as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let keys: Vec<_> = self.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let size = keys.len();
+        if size < 2 {
+            return None;
+        }
+
+        let mut matrix = vec![vec![0.0; size]; size];
+        for (from, key) in keys.iter().enumerate() {
+            let mut seen = HashSet::new();
+            for (edge, to) in self.neighbours(key).into_iter().flatten() {
+                match (index.get(to), self.get_edge(edge)) {
+                    (Some(&to), Some(data)) if to != from && seen.insert(edge) => {
+                        matrix[from][to] += weight(data);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut groups: Vec<Vec<usize>> = (0..size).map(|x| vec![x]).collect();
+        let mut active: Vec<_> = (0..size).collect();
+        let mut best: Option<(f64, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let mut connection = vec![0.0f64; size];
+            let mut added = vec![false; size];
+            let (mut previous, mut last) = (active[0], active[0]);
+            for _ in 0..active.len() {
+                let next = *active
+                    .iter()
+                    .filter(|&&x| !added[x])
+                    .max_by(|&&a, &&b| connection[a].total_cmp(&connection[b]))
+                    .unwrap();
+                added[next] = true;
+                previous = last;
+                last = next;
+                for &x in &active {
+                    connection[x] += matrix[next][x];
+                }
+            }
+
+            let phase = connection[last];
+            match &best {
+                Some((weight, _)) if *weight <= phase => {}
+                _ => best = Some((phase, groups[last].clone())),
+            }
+
+            let merged = std::mem::take(&mut groups[last]);
+            groups[previous].extend(merged);
+            for &x in &active {
+                matrix[previous][x] += matrix[last][x];
+                matrix[x][previous] = matrix[previous][x];
+            }
+            matrix[previous][previous] = 0.0;
+            active.retain(|&x| x != last);
+        }
+
+        let (weight, side) = best?;
+        let side: HashSet<_> = side.into_iter().map(|x| keys[x]).collect();
+        let rest = keys.into_iter().filter(|x| !side.contains(x)).collect();
+        Some(Cut {
+            weight,
+            partition: (side, rest),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn weighted(
+        size: usize,
+        edges: &[(usize, usize, f64)],
+    ) -> Oriented<Simple<usize, (), usize, f64>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to, weight)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *weight)).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn stoer_wagner() {
+        //The example from Stoer and Wagner's paper, with a minimum cut of weight 4.
+        let graph = weighted(
+            8,
+            &[
+                (0, 1, 2.0),
+                (0, 4, 3.0),
+                (1, 2, 3.0),
+                (1, 4, 2.0),
+                (1, 5, 2.0),
+                (2, 3, 4.0),
+                (2, 6, 2.0),
+                (3, 6, 2.0),
+                (3, 7, 2.0),
+                (4, 5, 3.0),
+                (5, 6, 1.0),
+                (6, 7, 3.0),
+            ],
+        );
+        let cut = graph.minimum_cut(|x| *x).unwrap();
+        assert!((cut.weight - 4.0).abs() < 1e-9);
+
+        let (left, right) = cut.partition;
+        let mut side: Vec<_> = if left.contains(&2) { left } else { right }
+            .into_iter()
+            .cloned()
+            .collect();
+        side.sort();
+        assert_eq!(side, vec![2, 3, 6, 7]);
+    }
+
+    #[test]
+    fn parallel_edges_and_loops() {
+        let graph = weighted(3, &[(0, 1, 1.0), (0, 1, 1.0), (1, 2, 5.0), (2, 2, 7.0)]);
+        let cut = graph.minimum_cut(|x| *x).unwrap();
+        assert!((cut.weight - 2.0).abs() < 1e-9);
+        assert_eq!(cut.partition.0.len() + cut.partition.1.len(), 3);
+    }
+
+    #[test]
+    fn disconnected() {
+        let graph = weighted(4, &[(0, 1, 1.0), (2, 3, 1.0)]);
+        let cut = graph.minimum_cut(|x| *x).unwrap();
+        assert_eq!(cut.weight, 0.0);
+        assert!(weighted(1, &[]).minimum_cut(|x| *x).is_none());
+    }
+}
@@ -1,66 +1,284 @@
 use crate::dev::orientation::{Directed, Undirected};
 use crate::dev::{Neighbours, Vertices};
-use crate::extended::take_random;
-use std::collections::{HashSet, VecDeque};
-use std::fmt::Debug;
+use crate::extended::topological_sort;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
-trait Cycle<'a, Orientation, Vertex> {
+///A closed walk, as pairs of a vertex and the edge leaving it towards the next vertex.
+pub type Walk<'a, VertexKey, EdgeKey> = Vec<(&'a VertexKey, &'a EdgeKey)>;
+
+///Detection of cycles, where a self loop is a cycle of its own.
+pub trait Cycle<'a, Orientation, VertexKey, EdgeKey> {
     fn cycle(&'a self) -> bool;
+
+    ///Any one cycle of the graph.
+    fn find_cycle(&'a self) -> Option<Walk<'a, VertexKey, EdgeKey>>;
 }
 
-impl<'a, Vertex, Graph> Cycle<'a, Directed, Vertex> for Graph
+impl<'a, VertexKey, EdgeKey, Graph> Cycle<'a, Directed, VertexKey, EdgeKey> for Graph
 where
-    Vertex: 'a + Eq + Hash,
-    Self: Vertices<'a, Item = Vertex> + Neighbours<'a, Directed, Vertex>,
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
 {
     fn cycle(&'a self) -> bool {
-        let mut queue = VecDeque::new();
-        let mut vertices: HashSet<_> = self.vertices().into_iter().collect();
-        while let Some(cluster) = take_random(&mut vertices) {
-            queue.push_back(cluster);
-            while let Some(vertex) = queue.pop_front() {
-                for (_, vert) in self.neighbours(vertex).into_iter().flatten() {
-                    if !vertices.remove(vert) {
-                        return true;
+        topological_sort(self).is_err()
+    }
+
+    fn find_cycle(&'a self) -> Option<Walk<'a, VertexKey, EdgeKey>> {
+        topological_sort(self).err()
+    }
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> Cycle<'a, Undirected, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn cycle(&'a self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    fn find_cycle(&'a self) -> Option<Walk<'a, VertexKey, EdgeKey>> {
+        fundamental_cycles(self, true).pop()
+    }
+}
+
+///Every elementary cycle of a directed graph, being those visiting no vertex twice.
+pub trait ElementaryCycles<'a, VertexKey, EdgeKey> {
+    ///Johnson's algorithm, finding each cycle once, starting at its earliest vertex in the order of the vertex iterator. Parallel edges give distinct cycles.
+    fn elementary_cycles(&'a self) -> Vec<Walk<'a, VertexKey, EdgeKey>>;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> ElementaryCycles<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn elementary_cycles(&'a self) -> Vec<Walk<'a, VertexKey, EdgeKey>> {
+        let keys: Vec<_> = self.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let adjacent: Vec<Vec<_>> = keys
+            .iter()
+            .map(|key| {
+                self.neighbours(key)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(edge, to)| Some((edge, *index.get(to)?)))
+                    .collect()
+            })
+            .collect();
+
+        let mut johnson = Johnson {
+            adjacent: &adjacent,
+            start: 0,
+            component: vec![false; keys.len()],
+            blocked: vec![false; keys.len()],
+            blocking: vec![HashSet::new(); keys.len()],
+            path: Vec::new(),
+            cycles: Vec::new(),
+        };
+        for start in 0..keys.len() {
+            johnson.start = start;
+            johnson.component = strongly_connected(&adjacent, start);
+            for vertex in start..keys.len() {
+                johnson.blocked[vertex] = false;
+                johnson.blocking[vertex].clear();
+            }
+            johnson.circuit();
+        }
+
+        johnson
+            .cycles
+            .into_iter()
+            .map(|cycle| cycle.into_iter().map(|(v, e)| (keys[v], e)).collect())
+            .collect()
+    }
+}
+
+struct Johnson<'b, 'a, EdgeKey> {
+    adjacent: &'b [Vec<(&'a EdgeKey, usize)>],
+    start: usize,
+    component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocking: Vec<HashSet<usize>>,
+    path: Vec<(usize, &'a EdgeKey)>,
+    cycles: Vec<Vec<(usize, &'a EdgeKey)>>,
+}
+
+impl<'b, 'a, EdgeKey> Johnson<'b, 'a, EdgeKey> {
+    ///Walks every path from the start, blocking vertices which can not currently lead back to it.
+    ///Each frame of the stack holds a vertex of the path, the index of its next edge, and whether a cycle passed through it.
+    fn circuit(&mut self) {
+        self.blocked[self.start] = true;
+        let mut stack = vec![(self.start, 0, false)];
+        while let Some(frame) = stack.last_mut() {
+            let vertex = frame.0;
+            if let Some(&(edge, to)) = self.adjacent[vertex].get(frame.1) {
+                frame.1 += 1;
+                if !self.component[to] {
+                    continue;
+                }
+                if to == self.start {
+                    let mut cycle = self.path.clone();
+                    cycle.push((vertex, edge));
+                    self.cycles.push(cycle);
+                    frame.2 = true;
+                } else if !self.blocked[to] {
+                    self.path.push((vertex, edge));
+                    self.blocked[to] = true;
+                    stack.push((to, 0, false));
+                }
+                continue;
+            }
+
+            let (_, _, found) = stack.pop().unwrap();
+            if found {
+                self.unblock(vertex);
+            } else {
+                for &(_, to) in self.adjacent[vertex].iter() {
+                    if self.component[to] {
+                        self.blocking[to].insert(vertex);
                     }
-                    queue.push_back(vert);
+                }
+            }
+            if let Some(parent) = stack.last_mut() {
+                parent.2 |= found;
+                self.path.pop();
+            }
+        }
+    }
+
+    fn unblock(&mut self, vertex: usize) {
+        let mut stack = vec![vertex];
+        while let Some(vertex) = stack.pop() {
+            self.blocked[vertex] = false;
+            for other in self.blocking[vertex].drain() {
+                if self.blocked[other] {
+                    stack.push(other);
                 }
             }
         }
-        false
     }
 }
 
-impl<'a, Vertex, Graph> Cycle<'a, Undirected, Vertex> for Graph
+///The vertices in the strongly connected component of the start, within the sub graph of vertices not preceding it.
+fn strongly_connected<EdgeKey>(adjacent: &[Vec<(&EdgeKey, usize)>], start: usize) -> Vec<bool> {
+    let mut incoming = vec![Vec::new(); adjacent.len()];
+    for (from, neighbours) in adjacent.iter().enumerate().skip(start) {
+        for &(_, to) in neighbours {
+            incoming[to].push(from);
+        }
+    }
+
+    let reach = |next: &dyn Fn(usize) -> Vec<usize>| {
+        let mut reached = vec![false; adjacent.len()];
+        reached[start] = true;
+        let mut stack = vec![start];
+        while let Some(vertex) = stack.pop() {
+            for to in next(vertex) {
+                if to >= start && !reached[to] {
+                    reached[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+        reached
+    };
+    let forward = reach(&|x| adjacent[x].iter().map(|y| y.1).collect());
+    let backward = reach(&|x| incoming[x].clone());
+    forward.iter().zip(backward).map(|(a, b)| *a && b).collect()
+}
+
+///A set of cycles of an undirected graph, from which every cycle can be formed by symmetric difference.
+pub trait CycleBasis<'a, VertexKey, EdgeKey> {
+    ///The fundamental cycles of a breadth first spanning forest, one for each edge outside of it.
+    fn cycle_basis(&'a self) -> Vec<Walk<'a, VertexKey, EdgeKey>>;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> CycleBasis<'a, VertexKey, EdgeKey> for Graph
 where
-    Vertex: 'a + Eq + Hash + Debug,
-    Self: Vertices<'a, Item = Vertex> + Neighbours<'a, Undirected, Vertex>,
-    <Self as Neighbours<'a, Undirected, Vertex>>::Edge: Hash + Eq + Debug,
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
 {
-    fn cycle(&'a self) -> bool {
-        let mut queue = VecDeque::new();
+    fn cycle_basis(&'a self) -> Vec<Walk<'a, VertexKey, EdgeKey>> {
+        fundamental_cycles(self, false)
+    }
+}
+
+///Closes a cycle for every edge outside a breadth first spanning forest, stopping after the first if asked to.
+fn fundamental_cycles<'a, Graph, VertexKey, EdgeKey>(
+    graph: &'a Graph,
+    first: bool,
+) -> Vec<Walk<'a, VertexKey, EdgeKey>>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    let mut parent: HashMap<&'a VertexKey, (&'a VertexKey, &'a EdgeKey)> = HashMap::new();
+    let mut depth = HashMap::new();
+    let mut used = HashSet::new();
+    let mut cycles = Vec::new();
 
-        let mut vertices: HashSet<_> = self.vertices().into_iter().collect();
+    for root in graph.vertices() {
+        if depth.contains_key(root) {
+            continue;
+        }
+        depth.insert(root, 0);
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
 
-        while let Some(cluster) = take_random(&mut vertices) {
-            queue.push_back((None, cluster));
+        while let Some(from) = queue.pop_front() {
+            for (edge, to) in graph.neighbours(from).into_iter().flatten() {
+                if !used.insert(edge) {
+                    continue;
+                }
+                if !depth.contains_key(to) {
+                    depth.insert(to, depth[from] + 1);
+                    parent.insert(to, (from, edge));
+                    queue.push_back(to);
+                    continue;
+                }
 
-            while let Some((from, vertex)) = queue.pop_front() {
-                vertices.remove(&vertex);
-                for (edge, vert) in self.neighbours(vertex).into_iter().flatten() {
-                    if let Some(true) = from.as_ref().map(|x| x == &edge) {
-                        continue;
-                    }
-                    if !vertices.contains(vert) {
-                        return true;
-                    }
-                    queue.push_back((Some(edge), vert));
+                let (mut a, mut b) = (from, to);
+                let mut down = Vec::new();
+                let mut up = Vec::new();
+                while depth[a] > depth[b] {
+                    let (p, e) = parent[a];
+                    down.push((p, e));
+                    a = p;
+                }
+                while depth[b] > depth[a] {
+                    let (p, e) = parent[b];
+                    up.push((b, e));
+                    b = p;
+                }
+                while a != b {
+                    let (pa, ea) = parent[a];
+                    let (pb, eb) = parent[b];
+                    down.push((pa, ea));
+                    up.push((b, eb));
+                    a = pa;
+                    b = pb;
+                }
+                down.reverse();
+                down.push((from, edge));
+                down.extend(up);
+                cycles.push(down);
+                if first {
+                    return cycles;
                 }
             }
         }
-        false
     }
+    cycles
 }
 
 #[cfg(test)]
@@ -69,7 +287,40 @@ mod tests {
     use crate::dev::orientation::AddEdge;
     use crate::dev::simple::Simple;
     use crate::dev::AddVertex;
-    use crate::wrapper::oriented::Orient;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn graph<O: crate::dev::orientation::Orientation>(
+        orientation: O,
+        size: usize,
+        edges: &[(usize, usize)],
+    ) -> Oriented<Simple<usize, (), usize, ()>, O>
+    where
+        Simple<usize, (), usize, ()>: AddEdge<O, usize, (usize, ()), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    ///Checks that every edge of the cycle leaves its vertex and enters the next one.
+    fn closed<O>(
+        graph: &Oriented<Simple<usize, (), usize, ()>, O>,
+        cycle: &[(&usize, &usize)],
+        directed: bool,
+    ) -> bool {
+        (0..cycle.len()).all(|i| {
+            let (vertex, edge) = cycle[i];
+            let next = cycle[(i + 1) % cycle.len()].0;
+            let node = &graph.edges[edge];
+            (node.from == *vertex && node.to == *next)
+                || (!directed && node.to == *vertex && node.from == *next)
+        })
+    }
 
     #[test]
     fn simple_directed_cycle() {
@@ -77,7 +328,8 @@ mod tests {
         let a = graph.add_vertex((0, ())).unwrap();
         graph.add_edge(&a, &a, (0, ())).unwrap();
 
-        assert!(graph.cycle())
+        assert!(graph.cycle());
+        assert_eq!(graph.find_cycle(), Some(vec![(&0, &0)]));
     }
     #[test]
     fn simple_undirected_cycle() {
@@ -85,7 +337,8 @@ mod tests {
         let a = graph.add_vertex((0, ())).unwrap();
         graph.add_edge(&a, &a, (0, ())).unwrap();
 
-        assert!(graph.cycle())
+        assert!(graph.cycle());
+        assert_eq!(graph.find_cycle(), Some(vec![(&0, &0)]));
     }
     #[test]
     fn simple_undirected_non_cycle() {
@@ -99,4 +352,74 @@ mod tests {
         dbg!(&graph);
         assert!(!graph.cycle())
     }
+
+    #[test]
+    fn diamond() {
+        let directed = graph(Directed, 4, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert!(!directed.cycle());
+        assert_eq!(directed.find_cycle(), None);
+        assert!(directed.elementary_cycles().is_empty());
+
+        let undirected = graph(Undirected, 4, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        let cycle = undirected.find_cycle().unwrap();
+        assert_eq!(cycle.len(), 4);
+        assert!(closed(&undirected, &cycle, false));
+    }
+
+    #[test]
+    fn directed_witness() {
+        let graph = graph(Directed, 4, &[(0, 1), (1, 2), (2, 3), (3, 1)]);
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert!(closed(&graph, &cycle, true));
+    }
+
+    #[test]
+    fn johnson() {
+        let graph = graph(
+            Directed,
+            3,
+            &[(0, 1), (1, 0), (1, 2), (2, 0), (2, 2), (1, 2)],
+        );
+        let cycles = graph.elementary_cycles();
+        assert!(cycles.iter().all(|x| closed(&graph, x, true)));
+
+        let mut lengths: Vec<_> = cycles.iter().map(|x| x.len()).collect();
+        lengths.sort();
+        assert_eq!(lengths, vec![1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn johnson_complete() {
+        let edges: Vec<_> = (0..5)
+            .flat_map(|a| (0..5).filter(move |&b| a != b).map(move |b| (a, b)))
+            .collect();
+        let graph = graph(Directed, 5, &edges);
+        let cycles = graph.elementary_cycles();
+        assert!(cycles.iter().all(|x| closed(&graph, x, true)));
+        assert_eq!(cycles.len(), 10 + 10 * 2 + 5 * 6 + 24);
+    }
+
+    #[test]
+    fn cycle_basis() {
+        let graph = graph(
+            Undirected,
+            5,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 2),
+                (4, 4),
+                (0, 1),
+            ],
+        );
+        let basis = graph.cycle_basis();
+        assert_eq!(basis.len(), 4);
+        assert!(basis.iter().all(|x| closed(&graph, x, false)));
+
+        assert_eq!(basis.iter().filter(|x| x.len() == 1).count(), 1);
+    }
 }
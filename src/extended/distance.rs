@@ -0,0 +1,202 @@
+use crate::dev::{orientation, GetEdge, Neighbours, Vertices};
+use crate::extended::path::breadth::Breadth;
+use crate::extended::path::dijkstra::Dijkstra;
+use crate::extended::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+///The greatest distance from every vertex to any other vertex, of a non empty and (strongly) connected graph.
+///Only built by Eccentricities, such that it always holds at least one vertex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Eccentricity<'a, VertexKey, Weight>
+where
+    VertexKey: Eq + Hash,
+{
+    eccentricity: HashMap<&'a VertexKey, Weight>,
+}
+
+impl<'a, VertexKey, Weight> Eccentricity<'a, VertexKey, Weight>
+where
+    VertexKey: Eq + Hash,
+    Weight: Ord + Clone,
+{
+    ///The eccentricity of every vertex.
+    pub fn eccentricities(&self) -> &HashMap<&'a VertexKey, Weight> {
+        &self.eccentricity
+    }
+
+    ///The largest eccentricity.
+    pub fn diameter(&self) -> Weight {
+        self.eccentricity.values().max().cloned().unwrap()
+    }
+
+    ///The smallest eccentricity.
+    pub fn radius(&self) -> Weight {
+        self.eccentricity.values().min().cloned().unwrap()
+    }
+
+    ///The vertices whose eccentricity equals the radius.
+    pub fn center(&self) -> HashSet<&'a VertexKey> {
+        self.equal(&self.radius())
+    }
+
+    ///The vertices whose eccentricity equals the diameter.
+    pub fn periphery(&self) -> HashSet<&'a VertexKey> {
+        self.equal(&self.diameter())
+    }
+
+    fn equal(&self, weight: &Weight) -> HashSet<&'a VertexKey> {
+        self.eccentricity
+            .iter()
+            .filter(|x| x.1 == weight)
+            .map(|x| *x.0)
+            .collect()
+    }
+}
+
+///Distance summaries of a graph, being None for empty graphs or whenever some vertex cannot reach another.
+pub trait Eccentricities<'a, Orientation, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///Eccentricities counting the number of edges along shortest paths, found using breadth first search.
+    fn eccentricity(&'a self) -> Option<Eccentricity<'a, VertexKey, usize>>;
+
+    ///Eccentricities summing the edge weights along shortest paths, found using dijkstras algorithm.
+    fn weighted_eccentricity(
+        &'a self,
+    ) -> Option<Eccentricity<'a, VertexKey, <Self as GetEdge<EdgeKey>>::Output>>
+    where
+        Self: GetEdge<EdgeKey>,
+        <Self as GetEdge<EdgeKey>>::Output: 'a + Ord + Clone + Default + AddAssign;
+}
+
+impl<'a, Orientation, VertexKey, EdgeKey, Graph> Eccentricities<'a, Orientation, VertexKey, EdgeKey>
+    for Graph
+where
+    Orientation: orientation::Orientation,
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn eccentricity(&'a self) -> Option<Eccentricity<'a, VertexKey, usize>> {
+        let keys: Vec<_> = self.vertices().into_iter().collect();
+        if keys.is_empty() {
+            return None;
+        }
+        let mut eccentricity = HashMap::new();
+        for &from in &keys {
+            let mut breadth: Breadth<_, _, EdgeKey, Orientation> = Breadth::new(self, from);
+            let mut furthest = 0;
+            for &to in &keys {
+                furthest = furthest.max(breadth.to(to)?.len());
+            }
+            eccentricity.insert(from, furthest);
+        }
+        Some(Eccentricity { eccentricity })
+    }
+
+    fn weighted_eccentricity(
+        &'a self,
+    ) -> Option<Eccentricity<'a, VertexKey, <Self as GetEdge<EdgeKey>>::Output>>
+    where
+        Self: GetEdge<EdgeKey>,
+        <Self as GetEdge<EdgeKey>>::Output: 'a + Ord + Clone + Default + AddAssign,
+    {
+        let keys: Vec<_> = self.vertices().into_iter().collect();
+        if keys.is_empty() {
+            return None;
+        }
+        let mut eccentricity = HashMap::new();
+        for &from in &keys {
+            let mut dijkstra: Dijkstra<_, _, _, _, Orientation> = Dijkstra::new(self, from);
+            let mut furthest = Default::default();
+            for &to in &keys {
+                furthest = std::cmp::max(furthest, dijkstra.distance(to)?);
+            }
+            eccentricity.insert(from, furthest);
+        }
+        Some(Eccentricity { eccentricity })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::{AddEdge, Directed, Undirected};
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn graph<O: orientation::Orientation>(
+        orientation: O,
+        size: usize,
+        edges: &[(usize, usize, usize)],
+    ) -> Oriented<Simple<usize, (), usize, usize>, O>
+    where
+        Simple<usize, (), usize, usize>: AddEdge<O, usize, (usize, usize), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to, weight)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *weight)).unwrap();
+        }
+        graph
+    }
+
+    fn set(keys: &[usize]) -> HashSet<usize> {
+        keys.iter().cloned().collect()
+    }
+
+    fn owned(keys: HashSet<&usize>) -> HashSet<usize> {
+        keys.into_iter().cloned().collect()
+    }
+
+    #[test]
+    fn unweighted() {
+        //A path 0-1-2-3 with a pendant vertex 4 on 1.
+        let path = graph(Undirected, 5, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (1, 4, 1)]);
+        let eccentricity = path.eccentricity().unwrap();
+        assert_eq!(eccentricity.eccentricities()[&0], 3);
+        assert_eq!(eccentricity.eccentricities()[&1], 2);
+        assert_eq!(eccentricity.diameter(), 3);
+        assert_eq!(eccentricity.radius(), 2);
+        assert_eq!(owned(eccentricity.center()), set(&[1, 2]));
+        assert_eq!(owned(eccentricity.periphery()), set(&[0, 3, 4]));
+    }
+
+    #[test]
+    fn weighted() {
+        //A square where the heavy edge 0-3 is avoided by going around.
+        let square = graph(
+            Undirected,
+            4,
+            &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 10)],
+        );
+        let eccentricity = square.weighted_eccentricity().unwrap();
+        assert_eq!(eccentricity.eccentricities()[&0], 3);
+        assert_eq!(eccentricity.eccentricities()[&1], 2);
+        assert_eq!(eccentricity.diameter(), 3);
+        assert_eq!(eccentricity.radius(), 2);
+        assert_eq!(owned(eccentricity.center()), set(&[1, 2]));
+        assert_eq!(square.eccentricity().unwrap().diameter(), 2);
+    }
+
+    #[test]
+    fn disconnected() {
+        let cycle = graph(Directed, 3, &[(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+        assert_eq!(cycle.eccentricity().unwrap().diameter(), 2);
+        assert_eq!(cycle.weighted_eccentricity().unwrap().radius(), 2);
+
+        let open = graph(Directed, 3, &[(0, 1, 1), (1, 2, 1)]);
+        assert_eq!(open.eccentricity(), None);
+        assert_eq!(open.weighted_eccentricity(), None);
+
+        let empty = graph(Undirected, 0, &[]);
+        assert_eq!(empty.eccentricity(), None);
+    }
+}
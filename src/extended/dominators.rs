@@ -0,0 +1,372 @@
+use crate::dev::orientation::Directed;
+use crate::dev::{Neighbours, Vertices};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///The dominator tree of the vertices reachable from a root, where a vertex dominates another if every path from the root to the latter passes through it.
+#[derive(Clone, Debug)]
+pub struct Dominators<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    keys: Vec<&'a VertexKey>,
+    index: HashMap<&'a VertexKey, usize>,
+    root: usize,
+    idom: Vec<Option<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<'a, VertexKey> Dominators<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///Cooper, Harvey and Kennedy's iterative algorithm, over the given successors of every vertex.
+    fn new(keys: Vec<&'a VertexKey>, successors: Vec<Vec<usize>>, root: usize) -> Self {
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let size = keys.len();
+
+        let mut postorder = Vec::with_capacity(size);
+        let mut visited = vec![false; size];
+        visited[root] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some((vertex, next)) = stack.last_mut() {
+            let vertex = *vertex;
+            match successors[vertex].get(*next) {
+                Some(&to) => {
+                    *next += 1;
+                    if !visited[to] {
+                        visited[to] = true;
+                        stack.push((to, 0));
+                    }
+                }
+                None => {
+                    postorder.push(vertex);
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut number = vec![0; size];
+        for (i, &vertex) in postorder.iter().enumerate() {
+            number[vertex] = i;
+        }
+        let mut predecessors = vec![Vec::new(); size];
+        for (from, successors) in successors.iter().enumerate() {
+            if visited[from] {
+                for &to in successors {
+                    predecessors[to].push(from);
+                }
+            }
+        }
+
+        let mut idom = vec![None; size];
+        idom[root] = Some(root);
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while number[a] < number[b] {
+                    a = idom[a].unwrap();
+                }
+                while number[b] < number[a] {
+                    b = idom[b].unwrap();
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &vertex in postorder.iter().rev().filter(|&&x| x != root) {
+                let mut processed = predecessors[vertex].iter().filter(|&&x| idom[x].is_some());
+                let first = *processed.next().unwrap();
+                let new = processed.fold(first, |a, &b| intersect(&idom, a, b));
+                if idom[vertex] != Some(new) {
+                    idom[vertex] = Some(new);
+                    changed = true;
+                }
+            }
+        }
+
+        Self {
+            keys,
+            index,
+            root,
+            idom,
+            predecessors,
+        }
+    }
+
+    pub fn root(&self) -> &'a VertexKey {
+        self.keys[self.root]
+    }
+
+    ///The closest strict dominator of the given vertex. None for the root and unreachable vertices.
+    pub fn immediate_dominator(&self, vertex: &VertexKey) -> Option<&'a VertexKey> {
+        let vertex = *self.index.get(vertex)?;
+        if vertex == self.root {
+            return None;
+        }
+        self.idom[vertex].map(|x| self.keys[x])
+    }
+
+    ///Walks up the dominator tree, from the given vertex to the root. None if the vertex is unreachable.
+    pub fn dominators<'b>(
+        &'b self,
+        vertex: &VertexKey,
+    ) -> Option<DominatorIter<'b, 'a, VertexKey>> {
+        let vertex = *self.index.get(vertex)?;
+        self.idom[vertex]?;
+        Some(DominatorIter {
+            dominators: self,
+            next: Some(vertex),
+        })
+    }
+
+    ///Whether every path from the root to `b` passes through `a`. Every reachable vertex dominates itself.
+    pub fn dominates(&self, a: &VertexKey, b: &VertexKey) -> bool {
+        match self.dominators(b) {
+            Some(mut dominators) => dominators.any(|x| x == a),
+            None => false,
+        }
+    }
+
+    ///The vertices immediately dominated by the given vertex, being its children in the dominator tree.
+    pub fn children(&self, vertex: &VertexKey) -> Vec<&'a VertexKey> {
+        let vertex = match self.index.get(vertex) {
+            Some(&vertex) => vertex,
+            None => return Vec::new(),
+        };
+        (0..self.keys.len())
+            .filter(|&x| x != self.root && self.idom[x] == Some(vertex))
+            .map(|x| self.keys[x])
+            .collect()
+    }
+
+    ///The immediate dominator of every reachable vertex other than the root.
+    pub fn immediate_dominators(&self) -> HashMap<&'a VertexKey, &'a VertexKey> {
+        (0..self.keys.len())
+            .filter(|&x| x != self.root)
+            .filter_map(|x| Some((self.keys[x], self.keys[self.idom[x]?])))
+            .collect()
+    }
+
+    ///The vertices where the dominance of each vertex ends, being those it does not strictly dominate, but which has a predecessor it dominates.
+    pub fn frontiers(&self) -> HashMap<&'a VertexKey, HashSet<&'a VertexKey>> {
+        let mut frontiers: HashMap<_, _> = (0..self.keys.len())
+            .filter(|&x| self.idom[x].is_some())
+            .map(|x| (self.keys[x], HashSet::new()))
+            .collect();
+        for (vertex, predecessors) in self.predecessors.iter().enumerate() {
+            //The root has an implicit entry edge, and no dominator of its own to stop at.
+            let idom = match self.idom[vertex] {
+                _ if vertex == self.root => None,
+                Some(idom) if predecessors.len() > 1 => Some(idom),
+                _ => continue,
+            };
+            for &predecessor in predecessors {
+                let mut runner = predecessor;
+                while Some(runner) != idom {
+                    frontiers
+                        .get_mut(self.keys[runner])
+                        .unwrap()
+                        .insert(self.keys[vertex]);
+                    if runner == self.root {
+                        break;
+                    }
+                    runner = self.idom[runner].unwrap();
+                }
+            }
+        }
+        frontiers
+    }
+}
+
+pub struct DominatorIter<'b, 'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    dominators: &'b Dominators<'a, VertexKey>,
+    next: Option<usize>,
+}
+
+impl<'b, 'a, VertexKey> Iterator for DominatorIter<'b, 'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    type Item = &'a VertexKey;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = if current == self.dominators.root {
+            None
+        } else {
+            self.dominators.idom[current]
+        };
+        Some(self.dominators.keys[current])
+    }
+}
+
+///Dominance within directed graphs, as used for control flow analysis.
+pub trait Dominance<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///The dominators of the vertices reachable from the given root. None if the root is not in the graph.
+    fn dominators(&'a self, root: &VertexKey) -> Option<Dominators<'a, VertexKey>>;
+
+    ///The dominators of the reversed graph, such that a vertex post dominates another if every path from the latter to the given exit passes through it.
+    fn post_dominators(&'a self, exit: &VertexKey) -> Option<Dominators<'a, VertexKey>>;
+}
+
+impl<'a, VertexKey, Graph> Dominance<'a, VertexKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey>,
+{
+    fn dominators(&'a self, root: &VertexKey) -> Option<Dominators<'a, VertexKey>> {
+        let (keys, successors) = successors(self);
+        let root = keys.iter().position(|&x| x == root)?;
+        Some(Dominators::new(keys, successors, root))
+    }
+
+    fn post_dominators(&'a self, exit: &VertexKey) -> Option<Dominators<'a, VertexKey>> {
+        let (keys, successors) = successors(self);
+        let exit = keys.iter().position(|&x| x == exit)?;
+        let mut incoming = vec![Vec::new(); keys.len()];
+        for (from, successors) in successors.into_iter().enumerate() {
+            for to in successors {
+                incoming[to].push(from);
+            }
+        }
+        Some(Dominators::new(keys, incoming, exit))
+    }
+}
+
+fn successors<'a, Graph, VertexKey>(graph: &'a Graph) -> (Vec<&'a VertexKey>, Vec<Vec<usize>>)
+where
+    VertexKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey>,
+{
+    let keys: Vec<_> = graph.vertices().into_iter().collect();
+    let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+    let successors = keys
+        .iter()
+        .map(|key| {
+            graph
+                .neighbours(key)
+                .into_iter()
+                .flatten()
+                .filter_map(|(_, to)| index.get(to).cloned())
+                .collect()
+        })
+        .collect();
+    (keys, successors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    ///The control flow graph from Cooper, Harvey and Kennedy, along with an unreachable vertex.
+    fn control_flow() -> Oriented<Simple<usize, (), usize, ()>, Directed> {
+        let mut graph = Simple::default().orient(Directed);
+        for i in 0..7 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 4),
+            (2, 5),
+            (3, 4),
+            (4, 3),
+            (5, 4),
+            (6, 0),
+        ];
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn immediate_dominators() {
+        let graph = control_flow();
+        let dominators = graph.dominators(&0).unwrap();
+
+        let mut idom: Vec<_> = dominators.immediate_dominators().into_iter().collect();
+        idom.sort();
+        assert_eq!(idom, vec![(&1, &0), (&2, &0), (&3, &0), (&4, &0), (&5, &2)]);
+        assert_eq!(dominators.immediate_dominator(&0), None);
+        assert_eq!(dominators.immediate_dominator(&6), None);
+    }
+
+    #[test]
+    fn tree() {
+        let graph = control_flow();
+        let dominators = graph.dominators(&0).unwrap();
+
+        let path: Vec<_> = dominators.dominators(&5).unwrap().collect();
+        assert_eq!(path, vec![&5, &2, &0]);
+        assert!(dominators.dominates(&2, &5));
+        assert!(!dominators.dominates(&2, &4));
+        assert!(dominators.dominators(&6).is_none());
+
+        let mut children = dominators.children(&0);
+        children.sort();
+        assert_eq!(children, vec![&1, &2, &3, &4]);
+    }
+
+    #[test]
+    fn frontiers() {
+        let graph = control_flow();
+        let frontiers = graph.dominators(&0).unwrap().frontiers();
+
+        fn set(x: &[usize]) -> HashSet<&usize> {
+            x.iter().collect()
+        }
+        assert_eq!(frontiers[&1], set(&[3]));
+        assert_eq!(frontiers[&2], set(&[4]));
+        assert_eq!(frontiers[&3], set(&[4]));
+        assert_eq!(frontiers[&4], set(&[3]));
+        assert_eq!(frontiers[&5], set(&[4]));
+        assert!(frontiers[&0].is_empty());
+    }
+
+    #[test]
+    fn back_edge_into_root() {
+        let mut graph = Simple::default().orient(Directed);
+        for i in 0..3 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to)) in [(0, 1), (1, 0), (1, 2)].iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        let frontiers = graph.dominators(&0).unwrap().frontiers();
+        assert_eq!(frontiers[&0], [0].iter().collect());
+        assert_eq!(frontiers[&1], [0].iter().collect());
+        assert!(frontiers[&2].is_empty());
+    }
+
+    #[test]
+    fn post_dominators() {
+        let mut graph = Simple::default().orient(Directed);
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        let edges = [(0, 1), (0, 2), (1, 3), (2, 3)];
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+
+        let post = graph.post_dominators(&3).unwrap();
+        assert_eq!(post.immediate_dominator(&0), Some(&3));
+        assert_eq!(post.immediate_dominator(&1), Some(&3));
+        assert!(graph.dominators(&9).is_none());
+    }
+}
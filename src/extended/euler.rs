@@ -0,0 +1,306 @@
+use crate::dev::orientation::{Directed, Undirected};
+use crate::dev::{orientation, Neighbours, Vertices};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///Trails passing through every edge exactly once.
+pub trait Eulerian<'a, Orientation, VertexKey, EdgeKey> {
+    ///Whether the graph has a closed trail through every edge.
+    fn is_eulerian(&'a self) -> bool;
+
+    ///Whether the graph has a trail through every edge, closed or not.
+    fn has_eulerian_trail(&'a self) -> bool;
+
+    ///A closed trail through every edge, starting and ending at the same vertex.
+    fn eulerian_circuit(&'a self) -> Option<Vec<&'a EdgeKey>>;
+
+    ///A trail through every edge, being a circuit if one exists.
+    fn eulerian_trail(&'a self) -> Option<Vec<&'a EdgeKey>>;
+}
+
+///The edges of the graph numbered from 0, along with the edges leaving every vertex and where they lead. Each edge is listed once per end.
+struct DenseEdges<'a, EdgeKey> {
+    edges: Vec<&'a EdgeKey>,
+    adjacent: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a, EdgeKey> DenseEdges<'a, EdgeKey>
+where
+    EdgeKey: 'a + Eq + Hash,
+{
+    fn new<Graph, Orientation, VertexKey>(graph: &'a Graph) -> Self
+    where
+        VertexKey: 'a + Eq + Hash,
+        Orientation: orientation::Orientation,
+        Graph: Vertices<'a, Item = VertexKey>
+            + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+    {
+        let keys: Vec<_> = graph.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let mut edge_index = HashMap::new();
+        let mut edges = Vec::new();
+        let adjacent = keys
+            .iter()
+            .map(|key| {
+                let mut seen = HashSet::new();
+                graph
+                    .neighbours(key)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(edge, to)| {
+                        let to = *index.get(to)?;
+                        let edge = *edge_index.entry(edge).or_insert_with(|| {
+                            edges.push(edge);
+                            edges.len() - 1
+                        });
+                        if seen.insert(edge) {
+                            Some((edge, to))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { edges, adjacent }
+    }
+
+    ///Hierholzer's algorithm, splicing closed sub trails into the trail until every edge reachable from the start is used.
+    fn hierholzer(&self, start: Option<usize>) -> Option<Vec<&'a EdgeKey>> {
+        let start = match start {
+            Some(start) => start,
+            None => return Some(Vec::new()),
+        };
+        let mut used = vec![false; self.edges.len()];
+        let mut next = vec![0; self.adjacent.len()];
+        let mut trail = Vec::with_capacity(self.edges.len());
+        let mut stack = vec![(start, None)];
+
+        while let Some(&(vertex, edge)) = stack.last() {
+            let neighbours = &self.adjacent[vertex];
+            while next[vertex] < neighbours.len() && used[neighbours[next[vertex]].0] {
+                next[vertex] += 1;
+            }
+            match neighbours.get(next[vertex]) {
+                Some(&(edge, to)) => {
+                    used[edge] = true;
+                    stack.push((to, Some(edge)));
+                }
+                None => {
+                    stack.pop();
+                    trail.extend(edge.map(|x| self.edges[x]));
+                }
+            }
+        }
+
+        if trail.len() == self.edges.len() {
+            trail.reverse();
+            Some(trail)
+        } else {
+            None
+        }
+    }
+
+    ///Every vertex must have as many incoming as outgoing edges, except for a trail starting one edge short and ending one edge over.
+    fn directed_start(&self, circuit: bool) -> Option<Option<usize>> {
+        let mut incoming = vec![0isize; self.adjacent.len()];
+        for neighbours in &self.adjacent {
+            for &(_, to) in neighbours {
+                incoming[to] += 1;
+            }
+        }
+
+        let mut start = None;
+        let mut ends = 0;
+        for (vertex, neighbours) in self.adjacent.iter().enumerate() {
+            match neighbours.len() as isize - incoming[vertex] {
+                0 => {}
+                1 if start.is_none() && !circuit => start = Some(vertex),
+                -1 if ends == 0 && !circuit => ends += 1,
+                _ => return None,
+            }
+        }
+        if start.is_some() != (ends == 1) {
+            return None;
+        }
+        Some(start.or_else(|| self.adjacent.iter().position(|x| !x.is_empty())))
+    }
+
+    ///Every vertex must have even degree, with self loops counted twice, except for a trail starting and ending at the two vertices of odd degree.
+    fn undirected_start(&self, circuit: bool) -> Option<Option<usize>> {
+        let degree = |vertex: usize| {
+            let loops = self.adjacent[vertex]
+                .iter()
+                .filter(|x| x.1 == vertex)
+                .count();
+            self.adjacent[vertex].len() + loops
+        };
+        let odd: Vec<_> = (0..self.adjacent.len())
+            .filter(|&x| degree(x) % 2 == 1)
+            .collect();
+        match odd.len() {
+            0 => Some(self.adjacent.iter().position(|x| !x.is_empty())),
+            2 if !circuit => Some(Some(odd[0])),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> Eulerian<'a, Directed, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn is_eulerian(&'a self) -> bool {
+        Eulerian::<Directed, _, _>::eulerian_circuit(self).is_some()
+    }
+
+    fn has_eulerian_trail(&'a self) -> bool {
+        Eulerian::<Directed, _, _>::eulerian_trail(self).is_some()
+    }
+
+    fn eulerian_circuit(&'a self) -> Option<Vec<&'a EdgeKey>> {
+        let dense = DenseEdges::new::<_, Directed, _>(self);
+        dense.hierholzer(dense.directed_start(true)?)
+    }
+
+    fn eulerian_trail(&'a self) -> Option<Vec<&'a EdgeKey>> {
+        let dense = DenseEdges::new::<_, Directed, _>(self);
+        dense.hierholzer(dense.directed_start(false)?)
+    }
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> Eulerian<'a, Undirected, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a + Eq + Hash,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn is_eulerian(&'a self) -> bool {
+        Eulerian::<Undirected, _, _>::eulerian_circuit(self).is_some()
+    }
+
+    fn has_eulerian_trail(&'a self) -> bool {
+        Eulerian::<Undirected, _, _>::eulerian_trail(self).is_some()
+    }
+
+    fn eulerian_circuit(&'a self) -> Option<Vec<&'a EdgeKey>> {
+        let dense = DenseEdges::new::<_, Undirected, _>(self);
+        dense.hierholzer(dense.undirected_start(true)?)
+    }
+
+    fn eulerian_trail(&'a self) -> Option<Vec<&'a EdgeKey>> {
+        let dense = DenseEdges::new::<_, Undirected, _>(self);
+        dense.hierholzer(dense.undirected_start(false)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn graph<O: orientation::Orientation>(
+        orientation: O,
+        size: usize,
+        edges: &[(usize, usize)],
+    ) -> Oriented<Simple<usize, (), usize, ()>, O>
+    where
+        Simple<usize, (), usize, ()>: AddEdge<O, usize, (usize, ()), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    ///Checks that the trail uses every edge once, with each edge starting where the previous ended.
+    fn valid<O>(
+        graph: &Oriented<Simple<usize, (), usize, ()>, O>,
+        trail: &[&usize],
+        directed: bool,
+    ) -> bool {
+        let mut edges: Vec<_> = trail.iter().map(|&&x| x).collect();
+        edges.sort();
+        if edges != (0..graph.edges.len()).collect::<Vec<_>>() {
+            return false;
+        }
+
+        let first = match trail.first() {
+            Some(edge) => &graph.edges[edge],
+            None => return true,
+        };
+        let starts = if directed {
+            vec![first.from]
+        } else {
+            vec![first.from, first.to]
+        };
+        starts.into_iter().any(|mut at| {
+            trail.iter().all(|edge| {
+                let node = &graph.edges[edge];
+                if node.from == at {
+                    at = node.to;
+                } else if !directed && node.to == at {
+                    at = node.from;
+                } else {
+                    return false;
+                }
+                true
+            })
+        })
+    }
+
+    #[test]
+    fn undirected_parallel_edges() {
+        let graph = graph(Undirected, 3, &[(0, 1), (0, 1), (1, 2), (2, 1), (1, 1)]);
+        assert!(graph.is_eulerian());
+
+        let circuit = graph.eulerian_circuit().unwrap();
+        assert!(valid(&graph, &circuit, false));
+    }
+
+    #[test]
+    fn undirected_trail() {
+        let graph = graph(Undirected, 4, &[(0, 1), (1, 2), (2, 3), (3, 1)]);
+        assert!(!graph.is_eulerian());
+        assert!(graph.has_eulerian_trail());
+
+        let trail = graph.eulerian_trail().unwrap();
+        assert!(valid(&graph, &trail, false));
+    }
+
+    #[test]
+    fn directed() {
+        let circuit = graph(Directed, 3, &[(0, 1), (1, 2), (2, 0), (0, 0)]);
+        let trail = circuit.eulerian_circuit().unwrap();
+        assert!(valid(&circuit, &trail, true));
+
+        let open = graph(Directed, 3, &[(0, 1), (1, 2), (2, 0), (0, 2)]);
+        assert!(!open.is_eulerian());
+        let trail = open.eulerian_trail().unwrap();
+        assert_eq!(open.edges[trail[0]].from, 0);
+        assert!(valid(&open, &trail, true));
+
+        let reversed = graph(Directed, 3, &[(0, 1), (0, 2)]);
+        assert!(!reversed.has_eulerian_trail());
+    }
+
+    #[test]
+    fn disconnected() {
+        let graph = graph(Undirected, 4, &[(0, 1), (1, 0), (2, 3), (3, 2)]);
+        assert!(!graph.is_eulerian());
+        assert_eq!(graph.eulerian_trail(), None);
+
+        let empty = Simple::<usize, (), usize, ()>::default().orient(Undirected);
+        assert_eq!(empty.eulerian_circuit(), Some(Vec::new()));
+    }
+}
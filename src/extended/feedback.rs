@@ -0,0 +1,291 @@
+use crate::dev::orientation::{AddEdge, Directed};
+use crate::dev::{AddVertex, GetEdge, GetVertex, Neighbours, Vertices};
+use crate::wrapper::sub::PartialSubGraph;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///The most vertices a minimum feedback arc set is searched for on, as its time and memory grow exponentially.
+pub const MINIMUM_FEEDBACK_LIMIT: usize = 16;
+
+///Sets of edges whose removal leaves a directed graph without cycles.
+pub trait FeedbackArcSet<'a, VertexKey, EdgeKey>
+where
+    Self: Sized,
+    EdgeKey: Eq + Hash,
+{
+    ///A small feedback arc set, found with the heuristic of Eades, Lin and Smyth. The vertices are ordered by repeatedly
+    ///moving sinks to the back, sources to the front, and otherwise the vertex with the most outgoing edges over incoming ones
+    ///to the front. The edges pointing backwards in the order, along with every self loop, make up the set.
+    fn feedback_arc_set(&'a self) -> HashSet<&'a EdgeKey>;
+
+    ///A feedback arc set of minimum size, found by dynamic programming over the subsets of vertices. Takes exponential time and memory,
+    ///so None for graphs of more than MINIMUM_FEEDBACK_LIMIT vertices.
+    fn minimum_feedback_arc_set(&'a self) -> Option<HashSet<&'a EdgeKey>>;
+
+    ///A view of the graph without the given edges, following only the remaining ones.
+    fn without_arcs<Graph2>(
+        &'a self,
+        arcs: &HashSet<&'a EdgeKey>,
+    ) -> PartialSubGraph<'a, Self, Graph2>
+    where
+        Graph2: Default + AddVertex<(VertexKey, ())> + AddEdge<Directed, VertexKey, (EdgeKey, ())>;
+}
+
+///The vertices numbered from 0, along with every edge as the numbers of its ends. Self loops are kept aside.
+struct DenseArcs<'a, EdgeKey> {
+    size: usize,
+    edges: Vec<(usize, &'a EdgeKey, usize)>,
+    loops: Vec<&'a EdgeKey>,
+}
+
+impl<'a, EdgeKey> DenseArcs<'a, EdgeKey>
+where
+    EdgeKey: 'a + Eq + Hash,
+{
+    fn new<Graph, VertexKey>(graph: &'a Graph) -> Self
+    where
+        VertexKey: 'a + Eq + Hash,
+        Graph: Vertices<'a, Item = VertexKey>
+            + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+    {
+        let keys: Vec<_> = graph.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let mut seen = HashSet::new();
+        let mut edges = Vec::new();
+        let mut loops = Vec::new();
+        for (from, key) in keys.iter().enumerate() {
+            for (edge, to) in graph.neighbours(key).into_iter().flatten() {
+                match index.get(to) {
+                    Some(&to) if seen.insert(edge) => {
+                        if to == from {
+                            loops.push(edge);
+                        } else {
+                            edges.push((from, edge, to));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Self {
+            size: keys.len(),
+            edges,
+            loops,
+        }
+    }
+
+    ///The self loops, along with the edges pointing backwards in the order.
+    fn backwards(&self, order: &[usize]) -> HashSet<&'a EdgeKey> {
+        let mut position = vec![0; self.size];
+        for (i, &vertex) in order.iter().enumerate() {
+            position[vertex] = i;
+        }
+        self.edges
+            .iter()
+            .filter(|(from, _, to)| position[*from] > position[*to])
+            .map(|x| x.1)
+            .chain(self.loops.iter().cloned())
+            .collect()
+    }
+
+    fn eades_lin_smyth(&self) -> Vec<usize> {
+        let mut outgoing = vec![Vec::new(); self.size];
+        let mut incoming = vec![Vec::new(); self.size];
+        for &(from, _, to) in &self.edges {
+            outgoing[from].push(to);
+            incoming[to].push(from);
+        }
+        let mut out_degree: Vec<_> = outgoing.iter().map(|x| x.len() as isize).collect();
+        let mut in_degree: Vec<_> = incoming.iter().map(|x| x.len() as isize).collect();
+        let mut removed = vec![false; self.size];
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        let mut remaining = self.size;
+        while remaining > 0 {
+            let alive = (0..self.size).filter(|&x| !removed[x]);
+            let vertex = if let Some(sink) = alive.clone().find(|&x| out_degree[x] == 0) {
+                back.push(sink);
+                sink
+            } else if let Some(source) = alive.clone().find(|&x| in_degree[x] == 0) {
+                front.push(source);
+                source
+            } else {
+                let best = alive.max_by_key(|&x| out_degree[x] - in_degree[x]).unwrap();
+                front.push(best);
+                best
+            };
+            removed[vertex] = true;
+            remaining -= 1;
+            for &to in &outgoing[vertex] {
+                in_degree[to] -= 1;
+            }
+            for &from in &incoming[vertex] {
+                out_degree[from] -= 1;
+            }
+        }
+
+        back.reverse();
+        front.extend(back);
+        front
+    }
+
+    ///The cheapest order of every subset is found from the cheapest orders of the subsets one vertex smaller,
+    ///placing the missing vertex last and paying for its edges back into the subset.
+    fn exact(&self) -> Vec<usize> {
+        let mut weight = vec![vec![0usize; self.size]; self.size];
+        for &(from, _, to) in &self.edges {
+            weight[from][to] += 1;
+        }
+
+        let full = 1usize << self.size;
+        let mut cost = vec![usize::MAX; full];
+        let mut last = vec![0; full];
+        cost[0] = 0;
+        for subset in 0..full {
+            if cost[subset] == usize::MAX {
+                continue;
+            }
+            for vertex in (0..self.size).filter(|&x| subset & (1 << x) == 0) {
+                let back: usize = (0..self.size)
+                    .filter(|&x| subset & (1 << x) != 0)
+                    .map(|x| weight[vertex][x])
+                    .sum();
+                let next = subset | (1 << vertex);
+                if cost[subset] + back < cost[next] {
+                    cost[next] = cost[subset] + back;
+                    last[next] = vertex;
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.size);
+        let mut subset = full - 1;
+        while subset != 0 {
+            order.push(last[subset]);
+            subset &= !(1 << last[subset]);
+        }
+        order.reverse();
+        order
+    }
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> FeedbackArcSet<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash + Clone,
+    EdgeKey: 'a + Eq + Hash + Clone,
+    Graph: Vertices<'a, Item = VertexKey>
+        + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>
+        + GetVertex<VertexKey>
+        + GetEdge<EdgeKey>,
+{
+    fn feedback_arc_set(&'a self) -> HashSet<&'a EdgeKey> {
+        let dense = DenseArcs::new(self);
+        dense.backwards(&dense.eades_lin_smyth())
+    }
+
+    fn minimum_feedback_arc_set(&'a self) -> Option<HashSet<&'a EdgeKey>> {
+        let dense = DenseArcs::new(self);
+        if dense.size > MINIMUM_FEEDBACK_LIMIT {
+            return None;
+        }
+        Some(dense.backwards(&dense.exact()))
+    }
+
+    fn without_arcs<Graph2>(
+        &'a self,
+        arcs: &HashSet<&'a EdgeKey>,
+    ) -> PartialSubGraph<'a, Self, Graph2>
+    where
+        Graph2: Default + AddVertex<(VertexKey, ())> + AddEdge<Directed, VertexKey, (EdgeKey, ())>,
+    {
+        let mut sub = PartialSubGraph::new(self);
+        for vertex in self.vertices() {
+            sub.add_vertex(vertex).ok();
+        }
+        for from in self.vertices() {
+            for (edge, to) in self.neighbours(from).into_iter().flatten() {
+                if !arcs.contains(edge) {
+                    AddEdge::<Directed, _, _>::add_edge(&mut sub, from, to, edge.clone()).ok();
+                }
+            }
+        }
+        sub
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::simple::Simple;
+    use crate::extended::topological_sort;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn directed(
+        size: usize,
+        edges: &[(usize, usize)],
+    ) -> Oriented<Simple<usize, (), usize, ()>, Directed> {
+        let mut graph = Simple::default().orient(Directed);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    fn acyclic(
+        graph: &Oriented<Simple<usize, (), usize, ()>, Directed>,
+        arcs: &HashSet<&usize>,
+    ) -> bool {
+        let sub: PartialSubGraph<_, Simple<usize, (), usize, ()>> = graph.without_arcs(arcs);
+        topological_sort(&sub).is_ok()
+    }
+
+    #[test]
+    fn heuristic() {
+        //Two triangles sharing the edge 1 -> 2, along with a self loop.
+        let graph = directed(4, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 1), (0, 3), (3, 3)]);
+        let arcs = graph.feedback_arc_set();
+        assert!(acyclic(&graph, &arcs));
+        assert!(arcs.contains(&6));
+        assert!(arcs.len() <= 4);
+    }
+
+    #[test]
+    fn exact() {
+        let graph = directed(4, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 1), (0, 3), (3, 3)]);
+        let arcs = graph.minimum_feedback_arc_set().unwrap();
+        assert!(acyclic(&graph, &arcs));
+        assert_eq!(arcs, [1, 6].iter().collect());
+
+        //Parallel edges make breaking the cycle on the other side cheaper.
+        let parallel = directed(2, &[(0, 1), (0, 1), (1, 0)]);
+        assert_eq!(
+            parallel.minimum_feedback_arc_set(),
+            Some([2].iter().collect())
+        );
+    }
+
+    #[test]
+    fn limit() {
+        let edges: Vec<_> = (0..=MINIMUM_FEEDBACK_LIMIT)
+            .map(|x| (x, (x + 1) % (MINIMUM_FEEDBACK_LIMIT + 1)))
+            .collect();
+        let cycle = directed(MINIMUM_FEEDBACK_LIMIT + 1, &edges);
+        assert_eq!(cycle.minimum_feedback_arc_set(), None);
+        assert_eq!(cycle.feedback_arc_set().len(), 1);
+    }
+
+    #[test]
+    fn acyclic_graph() {
+        let graph = directed(4, &[(0, 1), (1, 2), (0, 2), (2, 3)]);
+        assert!(graph.feedback_arc_set().is_empty());
+        assert!(graph.minimum_feedback_arc_set().unwrap().is_empty());
+
+        let sub: PartialSubGraph<_, Simple<usize, (), usize, ()>> =
+            graph.without_arcs(&HashSet::new());
+        assert_eq!(sub.neighbours(&0).unwrap().len(), 2);
+    }
+}
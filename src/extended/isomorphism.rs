@@ -0,0 +1,594 @@
+use crate::dev::{orientation, GetEdge, GetVertex, Neighbours, Vertices};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+///Index based adjacency of a graph, with the edges running in both directions.
+struct Adjacency<'a, VertexKey, EdgeKey> {
+    keys: Vec<&'a VertexKey>,
+    outgoing: Vec<HashMap<usize, Vec<&'a EdgeKey>>>,
+    incoming: Vec<HashMap<usize, Vec<&'a EdgeKey>>>,
+}
+
+impl<'a, VertexKey, EdgeKey> Adjacency<'a, VertexKey, EdgeKey>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+{
+    fn new<Graph, Orientation>(graph: &'a Graph) -> Self
+    where
+        Orientation: orientation::Orientation,
+        Graph: Vertices<'a, Item = VertexKey>
+            + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+    {
+        let keys: Vec<_> = graph.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+
+        let mut outgoing = vec![HashMap::new(); keys.len()];
+        let mut incoming = vec![HashMap::new(); keys.len()];
+        for (from, key) in keys.iter().enumerate() {
+            for (edge, to) in graph.neighbours(key).into_iter().flatten() {
+                if let Some(&to) = index.get(to) {
+                    outgoing[from].entry(to).or_insert_with(Vec::new).push(edge);
+                    incoming[to].entry(from).or_insert_with(Vec::new).push(edge);
+                }
+            }
+        }
+
+        Self {
+            keys,
+            outgoing,
+            incoming,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn edges(&self) -> usize {
+        self.outgoing
+            .iter()
+            .flat_map(|x| x.values())
+            .map(Vec::len)
+            .sum()
+    }
+}
+
+///Search state of VF2. The terminal sets store the depth at which a vertex entered them, with 0 meaning not at all.
+struct State {
+    core: (Vec<Option<usize>>, Vec<Option<usize>>),
+    incoming: (Vec<usize>, Vec<usize>),
+    outgoing: (Vec<usize>, Vec<usize>),
+    depth: usize,
+}
+
+fn enter<EdgeKey>(
+    terminal: &mut [usize],
+    neighbours: &HashMap<usize, Vec<EdgeKey>>,
+    vertex: usize,
+    depth: usize,
+) {
+    for &neighbour in neighbours.keys().chain(Some(&vertex)) {
+        if terminal[neighbour] == 0 {
+            terminal[neighbour] = depth;
+        }
+    }
+}
+
+fn leave(terminal: &mut [usize], depth: usize) {
+    for entry in terminal.iter_mut().filter(|x| **x == depth) {
+        *entry = 0;
+    }
+}
+
+///Checks that every mapped neighbour on one side is connected by as many edges to the image of the vertex on the other side.
+fn consistent<EdgeKey, EdgeKey2>(
+    neighbours: &HashMap<usize, Vec<EdgeKey>>,
+    other: &HashMap<usize, Vec<EdgeKey2>>,
+    core: &[Option<usize>],
+    vertex: usize,
+    image: usize,
+    mut edge_match: impl FnMut(&[EdgeKey], &[EdgeKey2]) -> bool,
+) -> bool {
+    neighbours.iter().all(|(&neighbour, edges)| {
+        let mapped = if neighbour == vertex {
+            Some(image)
+        } else {
+            core[neighbour]
+        };
+        match mapped {
+            Some(mapped) => match other.get(&mapped) {
+                Some(other) => other.len() == edges.len() && edge_match(edges, other),
+                None => false,
+            },
+            None => true,
+        }
+    })
+}
+
+type Predicate<'a, T, R> = Box<dyn 'a + Fn(&T, &R) -> bool>;
+
+///VF2 matcher between a pattern graph and a target graph, optionally comparing the data associated with vertices and edges.
+pub struct Vf2<'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2> {
+    pattern: Adjacency<'a, VertexKey, EdgeKey>,
+    target: Adjacency<'a, VertexKey2, EdgeKey2>,
+    vertex_match: Predicate<'a, VertexKey, VertexKey2>,
+    edge_match: Predicate<'a, EdgeKey, EdgeKey2>,
+    graphs: (&'a Graph, &'a Graph2),
+}
+
+impl<'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2>
+    Vf2<'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2>
+where
+    VertexKey: 'a + Eq + Hash,
+    VertexKey2: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    EdgeKey2: 'a,
+{
+    pub fn new<Orientation>(pattern: &'a Graph, target: &'a Graph2) -> Self
+    where
+        Orientation: orientation::Orientation,
+        Graph: Vertices<'a, Item = VertexKey>
+            + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+        Graph2: Vertices<'a, Item = VertexKey2>
+            + Neighbours<'a, Orientation, VertexKey2, Edge = &'a EdgeKey2>,
+    {
+        Self {
+            pattern: Adjacency::new(pattern),
+            target: Adjacency::new(target),
+            vertex_match: Box::new(|_, _| true),
+            edge_match: Box::new(|_, _| true),
+            graphs: (pattern, target),
+        }
+    }
+
+    ///Only allows vertices to be mapped onto each other, if the given predicate holds for their data.
+    pub fn match_vertices<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetVertex<VertexKey>,
+        Graph2: GetVertex<VertexKey2>,
+        Function: 'a
+            + Fn(
+                &<Graph as GetVertex<VertexKey>>::Output,
+                &<Graph2 as GetVertex<VertexKey2>>::Output,
+            ) -> bool,
+    {
+        let (pattern, target) = self.graphs;
+        self.vertex_match =
+            Box::new(
+                move |a, b| match (pattern.get_vertex(a), target.get_vertex(b)) {
+                    (Some(a), Some(b)) => function(a, b),
+                    _ => false,
+                },
+            );
+        self
+    }
+
+    ///Only allows edges to be mapped onto each other, if the given predicate holds for their data.
+    pub fn match_edges<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetEdge<EdgeKey>,
+        Graph2: GetEdge<EdgeKey2>,
+        Function: 'a
+            + Fn(&<Graph as GetEdge<EdgeKey>>::Output, &<Graph2 as GetEdge<EdgeKey2>>::Output) -> bool,
+    {
+        let (pattern, target) = self.graphs;
+        self.edge_match = Box::new(
+            move |a, b| match (pattern.get_edge(a), target.get_edge(b)) {
+                (Some(a), Some(b)) => function(a, b),
+                _ => false,
+            },
+        );
+        self
+    }
+
+    ///A mapping between the vertices of the two graphs, preserving both edges and non-edges, if such a one exists.
+    pub fn isomorphism(&self) -> Option<HashMap<&'a VertexKey, &'a VertexKey2>> {
+        if self.pattern.len() != self.target.len() || self.pattern.edges() != self.target.edges() {
+            return None;
+        }
+        self.subgraph_isomorphisms().next()
+    }
+
+    pub fn is_isomorphic(&self) -> bool {
+        self.isomorphism().is_some()
+    }
+
+    ///Every mapping of the pattern onto an induced subgraph of the target.
+    pub fn subgraph_isomorphisms(
+        &self,
+    ) -> Embeddings<'_, 'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2> {
+        let (pattern, target) = (self.pattern.len(), self.target.len());
+        Embeddings {
+            matcher: self,
+            state: State {
+                core: (vec![None; pattern], vec![None; target]),
+                incoming: (vec![0; pattern], vec![0; target]),
+                outgoing: (vec![0; pattern], vec![0; target]),
+                depth: 0,
+            },
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+
+    fn edges_match(&self, edges: &[&EdgeKey], other: &[&EdgeKey2]) -> bool {
+        edges
+            .iter()
+            .all(|a| other.iter().any(|b| (self.edge_match)(a, b)))
+    }
+}
+
+struct Frame {
+    candidates: Vec<(usize, usize)>,
+    current: Option<(usize, usize)>,
+}
+
+///Iterator over the mappings found by VF2.
+pub struct Embeddings<'b, 'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2> {
+    matcher: &'b Vf2<'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2>,
+    state: State,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+impl<'b, 'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2>
+    Embeddings<'b, 'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2>
+where
+    VertexKey: 'a + Eq + Hash,
+    VertexKey2: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    EdgeKey2: 'a,
+{
+    fn candidates(&self) -> Vec<(usize, usize)> {
+        let state = &self.state;
+        let unmapped = |terminal: &[usize], core: &[Option<usize>]| -> Vec<usize> {
+            (0..core.len())
+                .filter(|&i| core[i].is_none() && terminal[i] != 0)
+                .collect()
+        };
+
+        let pairs = |pattern: Vec<usize>, target: Vec<usize>| match pattern.first() {
+            Some(&vertex) => target.into_iter().map(|x| (vertex, x)).collect(),
+            None => Vec::new(),
+        };
+
+        let outgoing = unmapped(&state.outgoing.0, &state.core.0);
+        if !outgoing.is_empty() {
+            return pairs(outgoing, unmapped(&state.outgoing.1, &state.core.1));
+        }
+        let incoming = unmapped(&state.incoming.0, &state.core.0);
+        if !incoming.is_empty() {
+            return pairs(incoming, unmapped(&state.incoming.1, &state.core.1));
+        }
+        let free =
+            |core: &[Option<usize>]| (0..core.len()).filter(|&i| core[i].is_none()).collect();
+        pairs(free(&state.core.0), free(&state.core.1))
+    }
+
+    ///Counts the unmapped neighbours of a vertex that are in the incoming, outgoing and neither terminal sets.
+    fn lookahead<EdgeKey3>(
+        vertex: usize,
+        outgoing: &HashMap<usize, Vec<EdgeKey3>>,
+        incoming: &HashMap<usize, Vec<EdgeKey3>>,
+        core: &[Option<usize>],
+        terminal: (&[usize], &[usize]),
+    ) -> (usize, usize, usize) {
+        let mut count = (0, 0, 0);
+        let mut neighbours: Vec<_> = outgoing.keys().chain(incoming.keys()).collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        for &neighbour in neighbours {
+            if neighbour == vertex || core[neighbour].is_some() {
+                continue;
+            }
+            let (incoming, outgoing) = (terminal.0[neighbour] != 0, terminal.1[neighbour] != 0);
+            if incoming {
+                count.0 += 1;
+            }
+            if outgoing {
+                count.1 += 1;
+            }
+            if !incoming && !outgoing {
+                count.2 += 1;
+            }
+        }
+        count
+    }
+
+    fn feasible(&self, vertex: usize, image: usize) -> bool {
+        let (matcher, state) = (self.matcher, &self.state);
+        let (pattern, target) = (&matcher.pattern, &matcher.target);
+
+        if !(matcher.vertex_match)(pattern.keys[vertex], target.keys[image]) {
+            return false;
+        }
+
+        let forward = |a: &[&EdgeKey], b: &[&EdgeKey2]| matcher.edges_match(a, b);
+        let backward = |_: &[&EdgeKey2], _: &[&EdgeKey]| true;
+        let consistent = consistent(
+            &pattern.outgoing[vertex],
+            &target.outgoing[image],
+            &state.core.0,
+            vertex,
+            image,
+            forward,
+        ) && consistent(
+            &pattern.incoming[vertex],
+            &target.incoming[image],
+            &state.core.0,
+            vertex,
+            image,
+            forward,
+        ) && consistent(
+            &target.outgoing[image],
+            &pattern.outgoing[vertex],
+            &state.core.1,
+            image,
+            vertex,
+            backward,
+        ) && consistent(
+            &target.incoming[image],
+            &pattern.incoming[vertex],
+            &state.core.1,
+            image,
+            vertex,
+            backward,
+        );
+        if !consistent {
+            return false;
+        }
+
+        let left = Self::lookahead(
+            vertex,
+            &pattern.outgoing[vertex],
+            &pattern.incoming[vertex],
+            &state.core.0,
+            (&state.incoming.0, &state.outgoing.0),
+        );
+        let right = Self::lookahead(
+            image,
+            &target.outgoing[image],
+            &target.incoming[image],
+            &state.core.1,
+            (&state.incoming.1, &state.outgoing.1),
+        );
+        left.0 <= right.0 && left.1 <= right.1 && left.2 <= right.2
+    }
+
+    fn push(&mut self, vertex: usize, image: usize) {
+        let (pattern, target) = (&self.matcher.pattern, &self.matcher.target);
+        let state = &mut self.state;
+        state.depth += 1;
+        state.core.0[vertex] = Some(image);
+        state.core.1[image] = Some(vertex);
+        enter(
+            &mut state.outgoing.0,
+            &pattern.outgoing[vertex],
+            vertex,
+            state.depth,
+        );
+        enter(
+            &mut state.incoming.0,
+            &pattern.incoming[vertex],
+            vertex,
+            state.depth,
+        );
+        enter(
+            &mut state.outgoing.1,
+            &target.outgoing[image],
+            image,
+            state.depth,
+        );
+        enter(
+            &mut state.incoming.1,
+            &target.incoming[image],
+            image,
+            state.depth,
+        );
+    }
+
+    fn pop(&mut self, vertex: usize, image: usize) {
+        let state = &mut self.state;
+        leave(&mut state.outgoing.0, state.depth);
+        leave(&mut state.incoming.0, state.depth);
+        leave(&mut state.outgoing.1, state.depth);
+        leave(&mut state.incoming.1, state.depth);
+        state.core.0[vertex] = None;
+        state.core.1[image] = None;
+        state.depth -= 1;
+    }
+
+    fn mapping(&self) -> HashMap<&'a VertexKey, &'a VertexKey2> {
+        let (pattern, target) = (&self.matcher.pattern, &self.matcher.target);
+        self.state
+            .core
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| Some((pattern.keys[i], target.keys[(*x)?])))
+            .collect()
+    }
+}
+
+impl<'b, 'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2> Iterator
+    for Embeddings<'b, 'a, Graph, Graph2, VertexKey, VertexKey2, EdgeKey, EdgeKey2>
+where
+    VertexKey: 'a + Eq + Hash,
+    VertexKey2: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    EdgeKey2: 'a,
+{
+    type Item = HashMap<&'a VertexKey, &'a VertexKey2>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if self.matcher.pattern.len() > self.matcher.target.len() {
+                return None;
+            }
+            if self.matcher.pattern.len() == 0 {
+                return Some(HashMap::new());
+            }
+            let candidates = self.candidates();
+            self.stack.push(Frame {
+                candidates,
+                current: None,
+            });
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            if let Some((vertex, image)) = frame.current.take() {
+                self.pop(vertex, image);
+            }
+
+            let (vertex, image) = match self.stack.last_mut().unwrap().candidates.pop() {
+                Some(pair) => pair,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            if !self.feasible(vertex, image) {
+                continue;
+            }
+
+            self.push(vertex, image);
+            self.stack.last_mut().unwrap().current = Some((vertex, image));
+            if self.state.depth == self.matcher.pattern.len() {
+                return Some(self.mapping());
+            }
+            let candidates = self.candidates();
+            self.stack.push(Frame {
+                candidates,
+                current: None,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::{AddEdge, Directed, Undirected};
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn graph<O: orientation::Orientation + Clone>(
+        orientation: O,
+        vertices: &[(i32, char)],
+        edges: &[(i32, i32)],
+    ) -> Oriented<Simple<i32, char, usize, ()>, O>
+    where
+        Simple<i32, char, usize, ()>: AddEdge<O, i32, (usize, ()), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for vertex in vertices {
+            graph.add_vertex(*vertex).unwrap();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn triangle() {
+        let vertices = [(0, 'a'), (1, 'a'), (2, 'a')];
+        let a = graph(Undirected, &vertices, &[(0, 1), (1, 2), (2, 0)]);
+        let b = graph(Undirected, &vertices, &[(1, 0), (0, 2), (2, 1)]);
+
+        let mapping = Vf2::new(&a, &b).isomorphism().unwrap();
+        assert_eq!(mapping.len(), 3);
+        assert_eq!(Vf2::new(&a, &b).subgraph_isomorphisms().count(), 6);
+    }
+
+    #[test]
+    fn path_and_star() {
+        let vertices = [(0, 'a'), (1, 'a'), (2, 'a'), (3, 'a')];
+        let path = graph(Undirected, &vertices, &[(0, 1), (1, 2), (2, 3)]);
+        let star = graph(Undirected, &vertices, &[(0, 1), (0, 2), (0, 3)]);
+
+        assert!(!Vf2::new(&path, &star).is_isomorphic());
+    }
+
+    #[test]
+    fn directed() {
+        let vertices = [(0, 'a'), (1, 'a'), (2, 'a')];
+        let cycle = graph(Directed, &vertices, &[(0, 1), (1, 2), (2, 0)]);
+        let reversed = graph(Directed, &vertices, &[(1, 0), (2, 1), (0, 2)]);
+        let acyclic = graph(Directed, &vertices, &[(0, 1), (1, 2), (0, 2)]);
+
+        let mapping = Vf2::new(&cycle, &reversed).isomorphism().unwrap();
+        for (from, to) in &[(0, 1), (1, 2), (2, 0)] {
+            let (from, to) = (mapping[from], mapping[to]);
+            assert!(reversed
+                .edges
+                .values()
+                .any(|x| &x.from == from && &x.to == to));
+        }
+        assert!(!Vf2::new(&cycle, &acyclic).is_isomorphic());
+    }
+
+    #[test]
+    fn vertex_data() {
+        let a = graph(Undirected, &[(0, 'a'), (1, 'b')], &[(0, 1)]);
+        let b = graph(Undirected, &[(0, 'b'), (1, 'a')], &[(0, 1)]);
+        let c = graph(Undirected, &[(0, 'b'), (1, 'b')], &[(0, 1)]);
+
+        let matcher = Vf2::new(&a, &b).match_vertices(|x, y| x == y);
+        let mapping = matcher.isomorphism().unwrap();
+        assert_eq!(mapping[&0], &1);
+        assert_eq!(mapping[&1], &0);
+
+        assert!(!Vf2::new(&a, &c)
+            .match_vertices(|x, y| x == y)
+            .is_isomorphic());
+    }
+
+    #[test]
+    fn edge_data() {
+        let mut a = Simple::default().orient(Directed);
+        let mut b = Simple::default().orient(Directed);
+        for graph in [&mut a, &mut b].iter_mut() {
+            graph.add_vertex((0, ())).unwrap();
+            graph.add_vertex((1, ())).unwrap();
+        }
+        a.add_edge(&0, &1, ("e", 1)).unwrap();
+        b.add_edge(&0, &1, ("e", 2)).unwrap();
+
+        assert!(Vf2::new(&a, &b).is_isomorphic());
+        assert!(!Vf2::new(&a, &b).match_edges(|x, y| x == y).is_isomorphic());
+    }
+
+    #[test]
+    fn induced_subgraphs() {
+        let vertices = [(0, 'a'), (1, 'a'), (2, 'a'), (3, 'a')];
+        let complete = graph(
+            Undirected,
+            &vertices,
+            &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)],
+        );
+        let square = graph(Undirected, &vertices, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let triangle = graph(Undirected, &vertices[..3], &[(0, 1), (1, 2), (2, 0)]);
+        let path = graph(Undirected, &vertices[..3], &[(0, 1), (1, 2)]);
+
+        assert_eq!(
+            Vf2::new(&triangle, &complete)
+                .subgraph_isomorphisms()
+                .count(),
+            24
+        );
+        assert_eq!(Vf2::new(&path, &square).subgraph_isomorphisms().count(), 8);
+        assert_eq!(
+            Vf2::new(&triangle, &square).subgraph_isomorphisms().count(),
+            0
+        );
+        assert_eq!(
+            Vf2::new(&path, &complete).subgraph_isomorphisms().count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{Neighbours, Vertices};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+///A set of vertex disjoint edges, as found by some matching algorithm.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Matching<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///The edge and vertex each matched vertex is paired up with.
+    pub mate: HashMap<&'a VertexKey, (&'a EdgeKey, &'a VertexKey)>,
+    vertices: usize,
+}
+
+impl<'a, VertexKey, EdgeKey> Matching<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+    EdgeKey: Eq + Hash,
+{
+    ///The keys of the matched edges.
+    pub fn edges(&self) -> HashSet<&'a EdgeKey> {
+        self.mate.values().map(|(edge, _)| *edge).collect()
+    }
+
+    ///The number of matched edges.
+    pub fn len(&self) -> usize {
+        self.mate.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mate.is_empty()
+    }
+
+    ///Checks whether every vertex of the graph is covered by the matching.
+    pub fn is_perfect(&self) -> bool {
+        self.mate.len() == self.vertices
+    }
+}
+
+///Maximum cardinality matching of an undirected graph, found using Edmonds' blossom algorithm.
+pub trait MaximumMatching<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    fn maximum_matching(&'a self) -> Matching<'a, VertexKey, EdgeKey>;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> MaximumMatching<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn maximum_matching(&'a self) -> Matching<'a, VertexKey, EdgeKey> {
+        let keys: Vec<_> = self.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+
+        let mut edges = HashMap::new();
+        let mut adjacent = vec![Vec::new(); keys.len()];
+        for (from, key) in keys.iter().enumerate() {
+            for (edge, to) in self.neighbours(key).into_iter().flatten() {
+                if let Some(&to) = index.get(to) {
+                    if from != to && edges.insert((from.min(to), from.max(to)), edge).is_none() {
+                        adjacent[from].push(to);
+                        adjacent[to].push(from);
+                    }
+                }
+            }
+        }
+
+        let mut blossom = Blossom::new(adjacent);
+        for root in 0..keys.len() {
+            if blossom.mate[root].is_none() {
+                if let Some(end) = blossom.find_path(root) {
+                    blossom.augment(end);
+                }
+            }
+        }
+
+        let mate = blossom
+            .mate
+            .iter()
+            .enumerate()
+            .filter_map(|(from, to)| {
+                let to = (*to)?;
+                let edge = edges[&(from.min(to), from.max(to))];
+                Some((keys[from], (edge, keys[to])))
+            })
+            .collect();
+
+        Matching {
+            mate,
+            vertices: keys.len(),
+        }
+    }
+}
+
+struct Blossom {
+    adjacent: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    blossom: Vec<bool>,
+}
+
+impl Blossom {
+    fn new(adjacent: Vec<Vec<usize>>) -> Self {
+        let size = adjacent.len();
+        Self {
+            adjacent,
+            mate: vec![None; size],
+            parent: vec![None; size],
+            base: (0..size).collect(),
+            used: vec![false; size],
+            blossom: vec![false; size],
+        }
+    }
+
+    fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.adjacent.len()];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            match self.mate[a].and_then(|m| self.parent[m]) {
+                Some(next) => a = next,
+                None => break,
+            }
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b].unwrap()].unwrap();
+        }
+    }
+
+    fn mark_path(&mut self, mut vertex: usize, base: usize, mut child: usize) {
+        while self.base[vertex] != base {
+            let mate = self.mate[vertex].unwrap();
+            self.blossom[self.base[vertex]] = true;
+            self.blossom[self.base[mate]] = true;
+            self.parent[vertex] = Some(child);
+            child = mate;
+            vertex = self.parent[mate].unwrap();
+        }
+    }
+
+    ///Searches for an augmenting path starting at the given root, returning its other end point.
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let size = self.adjacent.len();
+        self.used = vec![false; size];
+        self.parent = vec![None; size];
+        self.base = (0..size).collect();
+
+        self.used[root] = true;
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+
+        while let Some(vertex) = queue.pop_front() {
+            for index in 0..self.adjacent[vertex].len() {
+                let to = self.adjacent[vertex][index];
+                if self.base[vertex] == self.base[to] || self.mate[vertex] == Some(to) {
+                    continue;
+                }
+                if to == root || self.mate[to].and_then(|m| self.parent[m]).is_some() {
+                    let base = self.lowest_common_ancestor(vertex, to);
+                    self.blossom = vec![false; size];
+                    self.mark_path(vertex, base, to);
+                    self.mark_path(to, base, vertex);
+                    for i in 0..size {
+                        if self.blossom[self.base[i]] {
+                            self.base[i] = base;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to].is_none() {
+                    self.parent[to] = Some(vertex);
+                    match self.mate[to] {
+                        None => return Some(to),
+                        Some(mate) => {
+                            self.used[mate] = true;
+                            queue.push_back(mate);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn augment(&mut self, end: usize) {
+        let mut vertex = Some(end);
+        while let Some(current) = vertex {
+            let parent = self.parent[current].unwrap();
+            let next = self.mate[parent];
+            self.mate[current] = Some(parent);
+            self.mate[parent] = Some(current);
+            vertex = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::Orient;
+    use crate::wrapper::sub::SubGraph;
+
+    #[test]
+    fn triangle() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..3 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        graph.add_edge(&0, &1, ("a", ())).unwrap();
+        graph.add_edge(&1, &2, ("b", ())).unwrap();
+        graph.add_edge(&2, &0, ("c", ())).unwrap();
+
+        let matching = graph.maximum_matching();
+        assert_eq!(matching.len(), 1);
+        assert!(!matching.is_perfect());
+    }
+
+    #[test]
+    fn blossom() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..6 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for i in 0..5 {
+            graph.add_edge(&i, &((i + 1) % 5), (i, ())).unwrap();
+        }
+        graph.add_edge(&4, &5, (5, ())).unwrap();
+
+        let matching = graph.maximum_matching();
+        assert_eq!(matching.len(), 3);
+        assert!(matching.is_perfect());
+        assert!(matching.edges().contains(&5));
+    }
+
+    #[test]
+    fn self_loop() {
+        let mut graph = Simple::default().orient(Undirected);
+        graph.add_vertex((0, ())).unwrap();
+        graph.add_edge(&0, &0, ("a", ())).unwrap();
+
+        assert!(graph.maximum_matching().is_empty());
+    }
+
+    #[test]
+    fn sub_graph() {
+        let mut graph: Simple<_, _, _, ()> = Simple::default();
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for i in 0..3 {
+            AddEdge::<Undirected, _, _>::add_edge(&mut graph, &i, &(i + 1), (i, ())).unwrap();
+        }
+        assert!(graph.maximum_matching().is_perfect());
+
+        let keys = [0, 1, 2];
+        let mut sub: SubGraph<_, Simple<_, _, i32, ()>> = SubGraph::new(&graph);
+        for key in &keys {
+            sub.add_vertex(key).unwrap();
+        }
+
+        let matching = sub.maximum_matching();
+        assert_eq!(matching.len(), 1);
+        assert!(!matching.is_perfect());
+    }
+}
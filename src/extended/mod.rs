@@ -1,16 +1,154 @@
-use std::collections::HashSet;
+use crate::dev::orientation::Directed;
+use crate::dev::{orientation, Neighbours, Vertices};
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
+pub mod automaton;
+pub mod canonical;
+pub mod centrality;
 pub mod clique;
+pub mod coloring;
+pub mod community;
+pub mod cover;
+pub mod cut;
 pub mod cyclic;
+pub mod distance;
+pub mod dominators;
+pub mod euler;
+pub mod feedback;
 pub mod header;
+pub mod isomorphism;
+pub mod matching;
 pub mod path;
+pub mod schedule;
+pub mod steiner;
+pub mod structure;
+pub mod transitive;
+pub mod tsp;
+pub mod walk;
 
-fn take_random<V>(hash_set: &mut HashSet<V>) -> Option<V>
+///The neighbouring vertices of every vertex in the graph, leaving out self loops.
+fn adjacency<'a, Graph, Orientation, VertexKey>(
+    graph: &'a Graph,
+) -> HashMap<&'a VertexKey, HashSet<&'a VertexKey>>
 where
-    V: Clone + Eq + Hash,
+    VertexKey: 'a + Eq + Hash,
+    Orientation: orientation::Orientation,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey>,
 {
-    let value = hash_set.iter().next()?.clone();
-    hash_set.remove(&value);
-    Some(value)
+    graph
+        .vertices()
+        .into_iter()
+        .map(|vertex| {
+            let neighbours = graph
+                .neighbours(vertex)
+                .into_iter()
+                .flatten()
+                .map(|x| x.1)
+                .filter(|&x| x != vertex)
+                .collect();
+            (vertex, neighbours)
+        })
+        .collect()
+}
+
+///Orders the vertices by repeatedly removing one of minimum degree, such that each vertex has as few later neighbours as possible.
+fn degeneracy_ordering<'a, VertexKey>(
+    adjacent: &HashMap<&'a VertexKey, HashSet<&'a VertexKey>>,
+) -> Vec<&'a VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    let mut degree: HashMap<_, _> = adjacent.iter().map(|(&k, v)| (k, v.len())).collect();
+    let mut buckets = vec![Vec::new(); degree.values().max().map_or(0, |x| x + 1)];
+    for (&key, &d) in &degree {
+        buckets[d].push(key);
+    }
+
+    let mut removed = HashSet::new();
+    let mut order = Vec::with_capacity(adjacent.len());
+    let mut low = 0;
+    while order.len() < adjacent.len() {
+        let vertex = match buckets[low].pop() {
+            Some(vertex) => vertex,
+            None => {
+                low += 1;
+                continue;
+            }
+        };
+        if degree[vertex] != low || !removed.insert(vertex) {
+            continue;
+        }
+        order.push(vertex);
+        for neighbour in &adjacent[vertex] {
+            if !removed.contains(neighbour) {
+                let d = degree.get_mut(neighbour).unwrap();
+                *d -= 1;
+                buckets[*d].push(neighbour);
+                low = low.min(*d);
+            }
+        }
+    }
+    order
+}
+
+///The representative of the set containing x, within a disjoint set forest. Halves the path on the way up.
+fn find(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+///Orders the vertices such that every edge goes from an earlier to a later vertex, or returns a cycle as pairs of a vertex and its outgoing edge.
+#[allow(clippy::type_complexity)]
+fn topological_sort<'a, Graph, VertexKey, EdgeKey>(
+    graph: &'a Graph,
+) -> Result<Vec<&'a VertexKey>, Vec<(&'a VertexKey, &'a EdgeKey)>>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    let mut finished = HashSet::new();
+    let mut active = HashSet::new();
+    let mut order = Vec::new();
+
+    for root in graph.vertices() {
+        if finished.contains(root) {
+            continue;
+        }
+        let mut path: Vec<(&'a VertexKey, &'a EdgeKey)> = Vec::new();
+        let mut stack = vec![(root, graph.neighbours(root).into_iter().flatten())];
+        active.insert(root);
+
+        while let Some((vertex, neighbours)) = stack.last_mut() {
+            let vertex = *vertex;
+            match neighbours.next() {
+                Some((edge, to)) => {
+                    if active.contains(to) {
+                        let start = path.iter().position(|x| x.0 == to).unwrap_or(path.len());
+                        let mut cycle = path.split_off(start);
+                        cycle.push((vertex, edge));
+                        return Err(cycle);
+                    }
+                    if !finished.contains(to) {
+                        active.insert(to);
+                        path.push((vertex, edge));
+                        stack.push((to, graph.neighbours(to).into_iter().flatten()));
+                    }
+                }
+                None => {
+                    active.remove(vertex);
+                    finished.insert(vertex);
+                    order.push(vertex);
+                    stack.pop();
+                    path.pop();
+                }
+            }
+        }
+    }
+    order.reverse();
+    Ok(order)
 }
@@ -54,7 +54,7 @@ where
     {
         while let Some(from) = self.queue.pop_front() {
             for (edge, to) in self.graph.neighbours(from).into_iter().flatten() {
-                if !self.visited.contains_key(to) {
+                if to != self.from && !self.visited.contains_key(to) {
                     self.visited.insert(to, (from, edge));
                     self.queue.push_back(to);
                 }
@@ -179,4 +179,20 @@ mod tests {
 
         assert_eq!(breadth.to(&c), Some(vec![(&"V0", &"E0"), (&"V1", &"E1")]));
     }
+
+    #[test]
+    fn cycle() {
+        let mut connected = Simple::default().orient(Directed);
+
+        let a = connected.add_vertex(("V0", ())).unwrap();
+        let b = connected.add_vertex(("V1", ())).unwrap();
+
+        let _ = connected.add_edge(&a, &b, ("E0", ()));
+        let _ = connected.add_edge(&b, &a, ("E1", ()));
+
+        let mut breadth: Breadth<_, _, _, _> = connected.path(&a);
+
+        assert_eq!(breadth.to(&b), Some(vec![(&"V0", &"E0")]));
+        assert_eq!(breadth.to(&a), Some(vec![]));
+    }
 }
@@ -2,7 +2,7 @@ use crate::dev::{orientation, GetEdge, Neighbours};
 use crate::extended::header::Header;
 use crate::extended::path::{Path, PathFinder};
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::AddAssign;
@@ -12,6 +12,7 @@ pub struct Dijkstra<'a, Graph, Vertex, Edge, Weight, Orientation> {
     from: &'a Vertex,
     graph: &'a Graph,
     visited: HashMap<&'a Vertex, (Weight, &'a Vertex, &'a Edge)>,
+    settled: HashSet<&'a Vertex>,
     queue: BinaryHeap<Reverse<Header<Weight, &'a Vertex>>>,
     orientation: PhantomData<Orientation>,
 }
@@ -37,6 +38,7 @@ where
             from,
             graph,
             visited,
+            settled: HashSet::new(),
             queue,
             orientation: Default::default(),
         }
@@ -63,31 +65,49 @@ where
         <Graph as GetEdge<Edge>>::Output: Clone,
         Weight: Ord + Clone + AddAssign + AddAssign<<Graph as GetEdge<Edge>>::Output>,
     {
-        while let Some(Reverse(Header(mut weight, from))) = self.queue.pop() {
-            for (edge, to) in self.graph.neighbours(from).into_iter().flatten() {
-                if let Some((w, _, _)) = self.visited.get(from) {
-                    weight.add_assign(w.clone());
+        while let Some(Reverse(Header(weight, from))) = self.queue.pop() {
+            if !self.settled.insert(from) {
+                continue;
+            }
+            for (edge, next) in self.graph.neighbours(from).into_iter().flatten() {
+                if self.settled.contains(next) {
+                    continue;
                 }
-
+                let mut total = weight.clone();
                 if let Some(w) = self.graph.get_edge(edge) {
-                    weight.add_assign(w.clone());
+                    total.add_assign(w.clone());
                 }
-
-                if !self.visited.contains_key(to) {
-                    self.queue.push(Reverse(Header(weight.clone(), to)));
+                let shorter = match self.visited.get(next) {
+                    Some((w, _, _)) => total < *w,
+                    None => true,
+                };
+                if shorter {
+                    self.visited.insert(next, (total.clone(), from, edge));
+                    self.queue.push(Reverse(Header(total, next)));
                 }
-
-                self.visited.insert(to, (weight.clone(), from, edge));
             }
-            if let Some((max_weight, _, _)) = self.visited.get(to) {
-                if let Some(Reverse(Header(weight, _))) = self.queue.peek() {
-                    if max_weight < weight {
-                        break;
-                    }
-                }
+            if to == from {
+                break;
             }
         }
     }
+
+    ///The length of the shortest path to the given vertex, searching further if it has yet to be reached.
+    pub fn distance(&mut self, to: &'a Vertex) -> Option<Weight>
+    where
+        Orientation: orientation::Orientation,
+        Graph: Neighbours<'a, Orientation, Vertex, Edge = &'a Edge> + GetEdge<Edge>,
+        <Graph as GetEdge<Edge>>::Output: Clone,
+        Weight: Ord + Clone + Default + AddAssign + AddAssign<<Graph as GetEdge<Edge>>::Output>,
+    {
+        if to == self.from {
+            return Some(Default::default());
+        }
+        if !self.settled.contains(to) {
+            self.cache_path(to);
+        }
+        self.visited.get(to).map(|x| x.0.clone())
+    }
 }
 
 impl<'a, Vertex, Edge, Graph, Weight, Orientation> Path<'a, Vertex, Edge>
@@ -102,7 +122,7 @@ where
     type IntoIter = Vec<(&'a Vertex, &'a Edge)>;
 
     fn to(&mut self, to: &'a Vertex) -> Option<Self::IntoIter> {
-        if !self.visited.contains_key(to) {
+        if !self.settled.contains(to) {
             self.cache_path(to);
         }
         self.finder(to)
@@ -208,4 +228,27 @@ mod tests {
 
         assert_eq!(path.to(&c), Some(vec![(&"V0", &"E0"), (&"V1", &"E1")]));
     }
+
+    #[test]
+    fn distance() {
+        let mut connected = Simple::default().orient(Directed);
+
+        let a = connected.add_vertex(("V0", ())).unwrap();
+        let b = connected.add_vertex(("V1", ())).unwrap();
+        let c = connected.add_vertex(("V2", ())).unwrap();
+        let d = connected.add_vertex(("V3", ())).unwrap();
+
+        let _ = connected.add_edge(&a, &c, ("E0", 5));
+        let _ = connected.add_edge(&a, &b, ("E1", 1));
+        let _ = connected.add_edge(&b, &c, ("E2", 1));
+        let _ = connected.add_edge(&c, &a, ("E3", 1));
+
+        let mut path: Dijkstra<_, _, _, _, _> = connected.path(&a);
+
+        assert_eq!(path.distance(&a), Some(0));
+        assert_eq!(path.distance(&c), Some(2));
+        assert_eq!(path.distance(&b), Some(1));
+        assert_eq!(path.distance(&d), None);
+        assert_eq!(path.to(&c), Some(vec![(&"V0", &"E1"), (&"V1", &"E2")]));
+    }
 }
@@ -0,0 +1,234 @@
+use crate::dev::orientation::Directed;
+use crate::dev::{GetEdge, GetVertex, Neighbours, Vertices};
+use crate::extended::topological_sort;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+type Duration<'a, Key> = Box<dyn 'a + Fn(&Key) -> f64>;
+
+///Start times of the tasks of a directed acyclic graph, where a task may only start once every predecessor has finished
+///and the time of the edge between them has passed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///The earliest start of every task.
+    pub earliest: HashMap<&'a VertexKey, f64>,
+    ///The latest start of every task, which does not delay the whole schedule.
+    pub latest: HashMap<&'a VertexKey, f64>,
+    ///The time until every task has finished, being the length of the longest path.
+    pub makespan: f64,
+    ///The longest path, as pairs of a vertex and its outgoing edge, followed by the last vertex.
+    pub longest_path: (Vec<(&'a VertexKey, &'a EdgeKey)>, Option<&'a VertexKey>),
+}
+
+impl<'a, VertexKey, EdgeKey> Schedule<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///How long the start of the task can be delayed, without delaying the whole schedule.
+    pub fn slack(&self, vertex: &VertexKey) -> Option<f64> {
+        Some(self.latest.get(vertex)? - self.earliest.get(vertex)?)
+    }
+
+    ///The tasks along the longest path, none of which can be delayed without delaying the whole schedule.
+    pub fn critical_path(&self) -> Vec<&'a VertexKey> {
+        let (path, last) = &self.longest_path;
+        path.iter().map(|x| x.0).chain(*last).collect()
+    }
+}
+
+///Critical path scheduling of directed acyclic graphs, with durations on both vertices and edges.
+pub struct Scheduler<'a, Graph, VertexKey, EdgeKey> {
+    graph: &'a Graph,
+    vertex: Option<Duration<'a, VertexKey>>,
+    edge: Option<Duration<'a, EdgeKey>>,
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> Scheduler<'a, Graph, VertexKey, EdgeKey>
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            vertex: None,
+            edge: None,
+        }
+    }
+
+    ///The time every task takes, read from the vertices. Defaults to zero.
+    pub fn vertex_durations<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetVertex<VertexKey>,
+        Function: 'a + Fn(&<Graph as GetVertex<VertexKey>>::Output) -> f64,
+    {
+        let graph = self.graph;
+        self.vertex = Some(Box::new(move |vertex| {
+            graph.get_vertex(vertex).map_or(0.0, &function)
+        }));
+        self
+    }
+
+    ///The time between the end of a task and the start of its successor, read from the edges. Defaults to one, such that
+    ///the longest path counts the edges along it.
+    pub fn edge_durations<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetEdge<EdgeKey>,
+        Function: 'a + Fn(&<Graph as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let graph = self.graph;
+        self.edge = Some(Box::new(move |edge| {
+            graph.get_edge(edge).map_or(0.0, &function)
+        }));
+        self
+    }
+
+    ///Computes the schedule in topological order, or returns a cycle as pairs of a vertex and its outgoing edge.
+    #[allow(clippy::type_complexity)]
+    pub fn schedule(
+        &self,
+    ) -> Result<Schedule<'a, VertexKey, EdgeKey>, Vec<(&'a VertexKey, &'a EdgeKey)>> {
+        let order = topological_sort(self.graph)?;
+        let vertex = |x| self.vertex.as_ref().map_or(0.0, |f| f(x));
+        let edge = |x| self.edge.as_ref().map_or(1.0, |f| f(x));
+
+        let mut earliest: HashMap<_, f64> = order.iter().map(|&x| (x, 0.0)).collect();
+        let mut previous = HashMap::new();
+        for &from in &order {
+            let finish = earliest[from] + vertex(from);
+            for (key, to) in self.graph.neighbours(from).into_iter().flatten() {
+                let start = finish + edge(key);
+                if let Some(current) = earliest.get_mut(to) {
+                    if *current < start || !previous.contains_key(to) {
+                        *current = start;
+                        previous.insert(to, (from, key));
+                    }
+                }
+            }
+        }
+
+        let last = order.iter().cloned().max_by(|a, b| {
+            let a = earliest[a] + vertex(a);
+            let b = earliest[b] + vertex(b);
+            a.total_cmp(&b)
+        });
+        let makespan = last.map_or(0.0, |x| earliest[x] + vertex(x));
+
+        let mut latest = HashMap::new();
+        for &from in order.iter().rev() {
+            let finish = self
+                .graph
+                .neighbours(from)
+                .into_iter()
+                .flatten()
+                .filter_map(|(key, to)| Some(latest.get(to)? - edge(key)))
+                .fold(makespan, f64::min);
+            latest.insert(from, finish - vertex(from));
+        }
+
+        let mut path = Vec::new();
+        let mut at = last;
+        while let Some(&(from, key)) = at.and_then(|x| previous.get(x)) {
+            path.push((from, key));
+            at = Some(from);
+        }
+        path.reverse();
+
+        Ok(Schedule {
+            earliest,
+            latest,
+            makespan,
+            longest_path: (path, last),
+        })
+    }
+}
+
+pub trait Scheduling<'a, VertexKey, EdgeKey>
+where
+    Self: Sized,
+{
+    fn scheduler(&'a self) -> Scheduler<'a, Self, VertexKey, EdgeKey>;
+}
+
+impl<'a, Graph, VertexKey, EdgeKey> Scheduling<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn scheduler(&'a self) -> Scheduler<'a, Self, VertexKey, EdgeKey> {
+        Scheduler::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    ///Tasks with durations, and edges with the time between the tasks.
+    fn tasks(
+        durations: &[f64],
+        edges: &[(usize, usize, f64)],
+    ) -> Oriented<Simple<usize, f64, usize, f64>, Directed> {
+        let mut graph = Simple::default().orient(Directed);
+        for (i, duration) in durations.iter().enumerate() {
+            graph.add_vertex((i, *duration)).unwrap();
+        }
+        for (i, (from, to, lag)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *lag)).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn critical_path() {
+        //0 fans out to a short branch 1 and a long branch 2, which join again at 3.
+        let graph = tasks(
+            &[2.0, 3.0, 5.0, 1.0],
+            &[(0, 1, 0.0), (0, 2, 0.0), (1, 3, 0.0), (2, 3, 1.0)],
+        );
+        let schedule = graph
+            .scheduler()
+            .vertex_durations(|x| *x)
+            .edge_durations(|x| *x)
+            .schedule()
+            .unwrap();
+
+        assert_eq!(schedule.makespan, 9.0);
+        assert_eq!(schedule.earliest[&3], 8.0);
+        assert_eq!(schedule.latest[&1], 5.0);
+        assert_eq!(schedule.slack(&1), Some(3.0));
+        assert_eq!(schedule.slack(&2), Some(0.0));
+        assert_eq!(schedule.critical_path(), vec![&0, &2, &3]);
+        assert_eq!(schedule.longest_path.0, vec![(&0, &1), (&2, &3)]);
+    }
+
+    #[test]
+    fn unweighted() {
+        let graph = tasks(&[0.0; 4], &[(0, 1, 0.0), (1, 2, 0.0), (0, 2, 0.0)]);
+        let schedule = graph.scheduler().schedule().unwrap();
+        assert_eq!(schedule.makespan, 2.0);
+        assert_eq!(schedule.critical_path(), vec![&0, &1, &2]);
+        assert_eq!(schedule.slack(&3), Some(2.0));
+    }
+
+    #[test]
+    fn cycle() {
+        let graph = tasks(&[1.0; 3], &[(0, 1, 0.0), (1, 2, 0.0), (2, 1, 0.0)]);
+        let cycle = graph.scheduler().schedule().unwrap_err();
+        assert_eq!(cycle.len(), 2);
+
+        let empty = tasks(&[], &[]);
+        let schedule = empty.scheduler().schedule().unwrap();
+        assert_eq!(schedule.makespan, 0.0);
+        assert!(schedule.critical_path().is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::dev::orientation::{AddEdge, Undirected};
+use crate::dev::{AddVertex, GetEdge, GetVertex, Neighbours};
+use crate::extended::find;
+use crate::extended::path::dijkstra::Dijkstra;
+use crate::extended::path::Path;
+use crate::wrapper::sub::PartialSubGraph;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+///Trees connecting a set of terminal vertices in undirected graphs.
+pub trait Steiner<'a, VertexKey, EdgeKey>
+where
+    Self: Sized,
+    VertexKey: 'a,
+{
+    ///A tree containing every terminal, weighing at most twice the optimum, found with the algorithm of Kou, Markowsky and Berman.
+    ///The shortest paths between the terminals form a complete graph, whose minimum spanning tree is expanded back into paths,
+    ///spanned once more, and pruned of leaves which are not terminals. The view follows only the edges of the tree.
+    ///None is returned if some terminal cannot be reached.
+    fn steiner_tree<Graph2, Terminals>(
+        &'a self,
+        terminals: Terminals,
+    ) -> Option<PartialSubGraph<'a, Self, Graph2>>
+    where
+        Terminals: IntoIterator<Item = &'a VertexKey>,
+        Graph2:
+            Default + AddVertex<(VertexKey, ())> + AddEdge<Undirected, VertexKey, (EdgeKey, ())>;
+}
+
+impl<'a, VertexKey, EdgeKey, Weight, Graph> Steiner<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash + Clone,
+    EdgeKey: 'a + Eq + Hash + Clone,
+    Weight: 'a + Ord + Clone + Default + AddAssign,
+    Graph: Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>
+        + GetVertex<VertexKey>
+        + GetEdge<EdgeKey, Output = Weight>,
+{
+    fn steiner_tree<Graph2, Terminals>(
+        &'a self,
+        terminals: Terminals,
+    ) -> Option<PartialSubGraph<'a, Self, Graph2>>
+    where
+        Terminals: IntoIterator<Item = &'a VertexKey>,
+        Graph2:
+            Default + AddVertex<(VertexKey, ())> + AddEdge<Undirected, VertexKey, (EdgeKey, ())>,
+    {
+        let terminals: Vec<_> = terminals
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut finders: Vec<Dijkstra<_, _, _, _, Undirected>> =
+            terminals.iter().map(|x| Dijkstra::new(self, x)).collect();
+
+        //Prim's algorithm on the complete graph of shortest paths between the terminals.
+        let size = terminals.len();
+        let mut closure = vec![vec![Weight::default(); size]; size];
+        for (i, finder) in finders.iter_mut().enumerate() {
+            for (j, terminal) in terminals.iter().enumerate() {
+                closure[i][j] = finder.distance(terminal)?;
+            }
+        }
+        let mut parent = vec![0; size];
+        let mut added = vec![false; size];
+        let mut paths = HashMap::new();
+        for _ in 0..size {
+            let next = (0..size)
+                .filter(|&x| !added[x])
+                .min_by_key(|&x| &closure[parent[x]][x])?;
+            added[next] = true;
+            if next != parent[next] {
+                let mut at = terminals[next];
+                let mut path = finders[parent[next]].to(at)?;
+                while let Some((from, edge)) = path.pop() {
+                    paths.insert(edge, (from, at));
+                    at = from;
+                }
+            }
+            for x in (0..size).filter(|&x| !added[x]) {
+                if closure[next][x] < closure[parent[x]][x] {
+                    parent[x] = next;
+                }
+            }
+        }
+
+        //Kruskal's algorithm on the edges along the chosen paths.
+        let mut edges: Vec<_> = paths.into_iter().collect();
+        edges.sort_by_key(|x| self.get_edge(x.0));
+        let mut index = HashMap::new();
+        for (_, (from, to)) in &edges {
+            for vertex in &[from, to] {
+                let next = index.len();
+                index.entry(**vertex).or_insert(next);
+            }
+        }
+        let mut forest: Vec<_> = (0..index.len()).collect();
+        let mut tree = HashMap::new();
+        for (edge, (from, to)) in edges {
+            let (a, b) = (find(&mut forest, index[from]), find(&mut forest, index[to]));
+            if a != b {
+                forest[a] = b;
+                tree.insert(edge, (from, to));
+            }
+        }
+
+        //Leaves which are not terminals only add weight.
+        let required: HashSet<_> = terminals.iter().cloned().collect();
+        let mut degree: HashMap<_, usize> = HashMap::new();
+        for (from, to) in tree.values() {
+            *degree.entry(*from).or_default() += 1;
+            *degree.entry(*to).or_default() += 1;
+        }
+        loop {
+            let leaves: Vec<_> = tree
+                .iter()
+                .filter(|(_, (from, to))| {
+                    (degree[from] == 1 && !required.contains(from))
+                        || (degree[to] == 1 && !required.contains(to))
+                })
+                .map(|(edge, (from, to))| (*edge, *from, *to))
+                .collect();
+            if leaves.is_empty() {
+                break;
+            }
+            for (edge, from, to) in leaves {
+                if tree.remove(edge).is_some() {
+                    *degree.get_mut(from).unwrap() -= 1;
+                    *degree.get_mut(to).unwrap() -= 1;
+                }
+            }
+        }
+
+        let mut sub = PartialSubGraph::new(self);
+        let vertices: HashSet<_> = tree
+            .values()
+            .flat_map(|(from, to)| vec![*from, *to])
+            .chain(terminals)
+            .collect();
+        for vertex in vertices {
+            sub.add_vertex(vertex).ok()?;
+        }
+        for (edge, (from, to)) in tree {
+            AddEdge::<Undirected, _, _>::add_edge(&mut sub, from, to, edge.clone()).ok()?;
+        }
+        Some(sub)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::simple::Simple;
+    use crate::dev::{Edges, Vertices};
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn weighted(
+        size: usize,
+        edges: &[(usize, usize, u32)],
+    ) -> Oriented<Simple<usize, (), usize, u32>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to, weight)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *weight)).unwrap();
+        }
+        graph
+    }
+
+    type Tree<'a> = PartialSubGraph<
+        'a,
+        Oriented<Simple<usize, (), usize, u32>, Undirected>,
+        Simple<usize, (), usize, ()>,
+    >;
+
+    fn sorted<'a>(keys: impl IntoIterator<Item = &'a usize>) -> Vec<usize> {
+        let mut keys: Vec<_> = keys.into_iter().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn star() {
+        //Three terminals around a hub, cheaper to join through it than directly.
+        let graph = weighted(
+            5,
+            &[
+                (0, 3, 1),
+                (1, 3, 1),
+                (2, 3, 1),
+                (0, 1, 3),
+                (1, 2, 3),
+                (0, 2, 3),
+                (3, 4, 1),
+            ],
+        );
+        let tree: Tree = graph.steiner_tree(&[0, 1, 2]).unwrap();
+        assert_eq!(sorted(tree.edges()), vec![0, 1, 2]);
+        assert_eq!(sorted(tree.vertices()), vec![0, 1, 2, 3]);
+
+        let mut neighbours = sorted(tree.neighbours(&0).unwrap().into_iter().map(|x| x.1));
+        neighbours.dedup();
+        assert_eq!(neighbours, vec![3]);
+    }
+
+    #[test]
+    fn path() {
+        //The terminals at the ends of a path need every edge, while the branch to 4 is left out.
+        let graph = weighted(5, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (1, 4, 1)]);
+        let tree: Tree = graph.steiner_tree(&[0, 3]).unwrap();
+        assert_eq!(sorted(tree.edges()), vec![0, 1, 2]);
+
+        let single: Tree = graph.steiner_tree(&[4]).unwrap();
+        assert_eq!(sorted(single.vertices()), vec![4]);
+        assert_eq!(single.edges().count(), 0);
+    }
+
+    #[test]
+    fn unreachable() {
+        let graph = weighted(3, &[(0, 1, 1)]);
+        let tree: Option<Tree> = graph.steiner_tree(&[0, 2]);
+        assert!(tree.is_none());
+        let missing: Option<Tree> = graph.steiner_tree(&[7]);
+        assert!(missing.is_none());
+    }
+}
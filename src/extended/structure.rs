@@ -0,0 +1,228 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{Neighbours, Vertices};
+use crate::extended::adjacency;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+///Structural statistics of undirected graphs. Self loops and parallel edges are ignored.
+pub trait Structure<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///The number of triangles each vertex is part of.
+    fn triangles(&'a self) -> HashMap<&'a VertexKey, usize>;
+
+    ///The number of triangles within the graph.
+    fn triangle_count(&'a self) -> usize;
+
+    ///The fraction of pairs of neighbours of each vertex, which are neighbours themselves. Zero for vertices with less than two neighbours.
+    fn clustering(&'a self) -> HashMap<&'a VertexKey, f64>;
+
+    ///The mean of the clustering coefficients, being zero for the empty graph.
+    fn average_clustering(&'a self) -> f64;
+
+    ///The fraction of paths of length two, which are closed into triangles.
+    fn transitivity(&'a self) -> f64;
+
+    ///The largest k for each vertex, such that it is part of a sub graph where every vertex has at least k neighbours.
+    fn core_numbers(&'a self) -> HashMap<&'a VertexKey, usize>;
+
+    ///The vertices of the largest sub graph, where every vertex has at least k neighbours.
+    fn k_core(&'a self, k: usize) -> HashSet<&'a VertexKey>;
+}
+
+impl<'a, VertexKey, Graph> Structure<'a, VertexKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey>,
+{
+    fn triangles(&'a self) -> HashMap<&'a VertexKey, usize> {
+        let adjacent = adjacency::<_, Undirected, _>(self);
+        adjacent
+            .iter()
+            .map(|(&vertex, neighbours)| {
+                let closed: usize = neighbours
+                    .iter()
+                    .map(|x| adjacent[x].intersection(neighbours).count())
+                    .sum();
+                (vertex, closed / 2)
+            })
+            .collect()
+    }
+
+    fn triangle_count(&'a self) -> usize {
+        self.triangles().values().sum::<usize>() / 3
+    }
+
+    fn clustering(&'a self) -> HashMap<&'a VertexKey, f64> {
+        let adjacent = adjacency::<_, Undirected, _>(self);
+        self.triangles()
+            .into_iter()
+            .map(|(vertex, triangles)| {
+                let degree = adjacent[vertex].len() as f64;
+                let coefficient = if degree < 2.0 {
+                    0.0
+                } else {
+                    2.0 * triangles as f64 / (degree * (degree - 1.0))
+                };
+                (vertex, coefficient)
+            })
+            .collect()
+    }
+
+    fn average_clustering(&'a self) -> f64 {
+        let clustering = self.clustering();
+        if clustering.is_empty() {
+            0.0
+        } else {
+            clustering.values().sum::<f64>() / clustering.len() as f64
+        }
+    }
+
+    fn transitivity(&'a self) -> f64 {
+        let adjacent = adjacency::<_, Undirected, _>(self);
+        let triples: usize = adjacent
+            .values()
+            .map(|x| x.len() * x.len().saturating_sub(1) / 2)
+            .sum();
+        if triples == 0 {
+            0.0
+        } else {
+            self.triangles().values().sum::<usize>() as f64 / triples as f64
+        }
+    }
+
+    ///Batagelj and Zaversnik's algorithm, removing vertices in order of their remaining degree, kept sorted in buckets.
+    fn core_numbers(&'a self) -> HashMap<&'a VertexKey, usize> {
+        let adjacent = adjacency::<_, Undirected, _>(self);
+        let keys: Vec<_> = adjacent.keys().cloned().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let neighbours: Vec<Vec<_>> = keys
+            .iter()
+            .map(|x| adjacent[x].iter().map(|y| index[y]).collect())
+            .collect();
+
+        let size = keys.len();
+        let mut degree: Vec<_> = neighbours.iter().map(|x| x.len()).collect();
+        let max = degree.iter().cloned().max().unwrap_or(0);
+
+        //Bucket sort the vertices by degree, remembering where each bucket starts.
+        let mut start = vec![0; max + 2];
+        for &d in &degree {
+            start[d + 1] += 1;
+        }
+        for d in 1..start.len() {
+            start[d] += start[d - 1];
+        }
+        let mut order = vec![0; size];
+        let mut position = vec![0; size];
+        let mut next = start.clone();
+        for vertex in 0..size {
+            position[vertex] = next[degree[vertex]];
+            order[position[vertex]] = vertex;
+            next[degree[vertex]] += 1;
+        }
+
+        for i in 0..size {
+            let vertex = order[i];
+            for &neighbour in &neighbours[vertex] {
+                if degree[neighbour] > degree[vertex] {
+                    //Swap the neighbour to the front of its bucket, and move the bucket boundary past it.
+                    let d = degree[neighbour];
+                    let first = start[d].max(i + 1);
+                    let other = order[first];
+                    if other != neighbour {
+                        order.swap(position[neighbour], first);
+                        position[other] = position[neighbour];
+                        position[neighbour] = first;
+                    }
+                    start[d] = first + 1;
+                    degree[neighbour] -= 1;
+                }
+            }
+        }
+
+        keys.into_iter().zip(degree).collect()
+    }
+
+    fn k_core(&'a self, k: usize) -> HashSet<&'a VertexKey> {
+        self.core_numbers()
+            .into_iter()
+            .filter(|x| x.1 >= k)
+            .map(|x| x.0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    ///A square 0-1-2-3 with the diagonal 0-2, a pendant vertex 4 on 3, a self loop on 4, and a parallel edge 0-1.
+    fn graph() -> Oriented<Simple<usize, (), usize, ()>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..5 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 0),
+            (0, 2),
+            (3, 4),
+            (4, 4),
+            (0, 1),
+        ];
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn triangles() {
+        let graph = graph();
+        let triangles = graph.triangles();
+        assert_eq!(triangles[&0], 2);
+        assert_eq!(triangles[&1], 1);
+        assert_eq!(triangles[&3], 1);
+        assert_eq!(triangles[&4], 0);
+        assert_eq!(graph.triangle_count(), 2);
+    }
+
+    #[test]
+    fn clustering() {
+        let graph = graph();
+        let clustering = graph.clustering();
+        assert!((clustering[&0] - 2.0 / 3.0).abs() < 1e-9);
+        assert!((clustering[&1] - 1.0).abs() < 1e-9);
+        assert!((clustering[&3] - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(clustering[&4], 0.0);
+
+        let average = (2.0 / 3.0 + 1.0 + 2.0 / 3.0 + 1.0 / 3.0) / 5.0;
+        assert!((graph.average_clustering() - average).abs() < 1e-9);
+
+        //Six closed paths of length two, out of ten.
+        assert!((graph.transitivity() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cores() {
+        let graph = graph();
+        let cores = graph.core_numbers();
+        assert_eq!(cores[&0], 2);
+        assert_eq!(cores[&1], 2);
+        assert_eq!(cores[&2], 2);
+        assert_eq!(cores[&3], 2);
+        assert_eq!(cores[&4], 1);
+
+        let core: HashSet<_> = [0, 1, 2, 3].iter().collect();
+        assert_eq!(graph.k_core(2), core);
+        assert!(graph.k_core(3).is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::dev::orientation::{AddEdge, Directed};
+use crate::dev::simple::Simple;
+use crate::dev::{AddVertex, Neighbours, Vertices};
+use crate::extended::topological_sort;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+///Reachability within directed graphs.
+pub trait Transitive<'a, VertexKey, EdgeKey>
+where
+    VertexKey: Eq + Hash,
+{
+    ///A graph with an edge from every vertex to each vertex reachable from it, keyed by the two vertices. A vertex only reaches itself through a cycle.
+    fn transitive_closure(&'a self) -> Simple<VertexKey, (), (VertexKey, VertexKey), ()>;
+
+    ///The edges which can be removed without changing which vertices are reachable, or a cycle if the graph has one, as the reduction would then be ambiguous.
+    #[allow(clippy::type_complexity)]
+    fn transitive_reduction(
+        &'a self,
+    ) -> Result<HashSet<&'a EdgeKey>, Vec<(&'a VertexKey, &'a EdgeKey)>>;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> Transitive<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash + Clone,
+    EdgeKey: 'a + Eq + Hash,
+    Graph: Vertices<'a, Item = VertexKey> + Neighbours<'a, Directed, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn transitive_closure(&'a self) -> Simple<VertexKey, (), (VertexKey, VertexKey), ()> {
+        let mut closure = Simple::default();
+        for vertex in self.vertices() {
+            closure.add_vertex((vertex.clone(), ())).ok();
+        }
+
+        for from in self.vertices() {
+            let mut reached = HashSet::new();
+            let mut queue: VecDeque<_> = vec![from].into();
+            while let Some(vertex) = queue.pop_front() {
+                for (_, to) in self.neighbours(vertex).into_iter().flatten() {
+                    if reached.insert(to) {
+                        queue.push_back(to);
+                    }
+                }
+            }
+            for to in reached {
+                let key = (from.clone(), to.clone());
+                AddEdge::<Directed, _, _>::add_edge(&mut closure, from, to, (key, ())).ok();
+            }
+        }
+        closure
+    }
+
+    fn transitive_reduction(
+        &'a self,
+    ) -> Result<HashSet<&'a EdgeKey>, Vec<(&'a VertexKey, &'a EdgeKey)>> {
+        let order = topological_sort(self)?;
+        let mut descendants: HashMap<&'a VertexKey, HashSet<&'a VertexKey>> = HashMap::new();
+        let mut redundant = HashSet::new();
+
+        for vertex in order.into_iter().rev() {
+            let edges: Vec<_> = self.neighbours(vertex).into_iter().flatten().collect();
+            let mut reached = HashSet::new();
+            for (_, to) in &edges {
+                reached.extend(descendants[to].iter().cloned());
+            }
+            for (edge, to) in edges {
+                if !reached.insert(to) {
+                    redundant.insert(edge);
+                }
+            }
+            descendants.insert(vertex, reached);
+        }
+        Ok(redundant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::oriented::{Orient, Oriented};
+
+    fn directed(
+        size: usize,
+        edges: &[(usize, usize)],
+    ) -> Oriented<Simple<usize, (), usize, ()>, Directed> {
+        let mut graph = Simple::default().orient(Directed);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, ())).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn closure() {
+        let graph = directed(4, &[(0, 1), (1, 2), (3, 3)]);
+        let closure = graph.transitive_closure();
+
+        let mut edges: Vec<_> = closure.edges.keys().cloned().collect();
+        edges.sort();
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 2), (3, 3)]);
+        assert_eq!(closure.vertices.len(), 4);
+    }
+
+    #[test]
+    fn reduction() {
+        let graph = directed(4, &[(0, 1), (1, 2), (0, 2), (2, 3), (0, 3), (1, 2)]);
+        let redundant = graph.transitive_reduction().unwrap();
+
+        assert_eq!(redundant.len(), 3);
+        assert!(redundant.contains(&2));
+        assert!(redundant.contains(&4));
+        assert!(redundant.contains(&1) ^ redundant.contains(&5));
+    }
+
+    #[test]
+    fn cyclic() {
+        let graph = directed(3, &[(0, 1), (1, 2), (2, 0)]);
+        let cycle = graph.transitive_reduction().unwrap_err();
+        assert_eq!(cycle.len(), 3);
+
+        let looped = directed(1, &[(0, 0)]);
+        assert_eq!(looped.transitive_reduction(), Err(vec![(&0, &0)]));
+    }
+}
@@ -0,0 +1,941 @@
+use crate::dev::orientation::Undirected;
+use crate::dev::{GetEdge, Neighbours, Vertices};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+///Improvements smaller than this are ignored, such that rounding errors can not cause endless improvement.
+const EPSILON: f64 = 1e-9;
+
+///The integer the longest distance is scaled to when matching odd vertices in Christofides' algorithm.
+const PRECISION: i64 = 1 << 32;
+
+///The most vertices Held and Karp's algorithm is run on, as its time and memory grow exponentially.
+pub const HELD_KARP_LIMIT: usize = 16;
+
+///A closed tour visiting every vertex once, returning to the first vertex at the end.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tour<'a, VertexKey> {
+    pub vertices: Vec<&'a VertexKey>,
+    pub cost: f64,
+}
+
+///The distances between every pair of vertices of an undirected graph, as used by the travelling salesman heuristics.
+///Vertices without an edge between them are infinitely far apart. Parallel edges are reduced to the shortest one.
+pub struct Salesman<'a, VertexKey>
+where
+    VertexKey: Eq + Hash,
+{
+    keys: Vec<&'a VertexKey>,
+    index: HashMap<&'a VertexKey, usize>,
+    distance: Vec<Vec<f64>>,
+}
+
+impl<'a, VertexKey> Salesman<'a, VertexKey>
+where
+    VertexKey: 'a + Eq + Hash,
+{
+    pub fn new<Graph, EdgeKey, Function>(graph: &'a Graph, distance: Function) -> Self
+    where
+        EdgeKey: 'a,
+        Graph: Vertices<'a, Item = VertexKey>
+            + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>
+            + GetEdge<EdgeKey>,
+        Function: Fn(&<Graph as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let keys: Vec<_> = graph.vertices().into_iter().collect();
+        let index: HashMap<_, _> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+        let mut matrix = vec![vec![f64::INFINITY; keys.len()]; keys.len()];
+        for (from, key) in keys.iter().enumerate() {
+            matrix[from][from] = 0.0;
+            for (edge, to) in graph.neighbours(key).into_iter().flatten() {
+                let weight = match (index.get(to), graph.get_edge(edge)) {
+                    (Some(&to), Some(weight)) if to != from => (to, distance(weight)),
+                    _ => continue,
+                };
+                let current = &mut matrix[from][weight.0];
+                *current = current.min(weight.1);
+            }
+        }
+        Self {
+            keys,
+            index,
+            distance: matrix,
+        }
+    }
+
+    fn cost(&self, tour: &[usize]) -> f64 {
+        (0..tour.len())
+            .map(|i| self.distance[tour[i]][tour[(i + 1) % tour.len()]])
+            .sum()
+    }
+
+    fn tour(&self, tour: Vec<usize>) -> Option<Tour<'a, VertexKey>> {
+        let cost = self.cost(&tour);
+        if cost.is_finite() {
+            Some(Tour {
+                vertices: tour.into_iter().map(|x| self.keys[x]).collect(),
+                cost,
+            })
+        } else {
+            None
+        }
+    }
+
+    ///The indices of the vertices of the tour, None if any of them is not in the graph.
+    fn indices(&self, tour: &Tour<'a, VertexKey>) -> Option<Vec<usize>> {
+        tour.vertices
+            .iter()
+            .map(|x| self.index.get(x).cloned())
+            .collect()
+    }
+
+    ///Starts at the given vertex, repeatedly moving to the closest vertex not yet visited. None if the tour gets stuck.
+    pub fn nearest_neighbour(&self, start: &VertexKey) -> Option<Tour<'a, VertexKey>> {
+        let mut current = *self.index.get(start)?;
+        let mut visited = vec![false; self.keys.len()];
+        visited[current] = true;
+        let mut tour = vec![current];
+
+        while tour.len() < self.keys.len() {
+            let next = (0..self.keys.len())
+                .filter(|&x| !visited[x] && self.distance[current][x].is_finite())
+                .min_by(|&a, &b| self.distance[current][a].total_cmp(&self.distance[current][b]))?;
+            visited[next] = true;
+            tour.push(next);
+            current = next;
+        }
+        self.tour(tour)
+    }
+
+    ///Repeatedly replaces two edges of the tour by two shorter ones, reversing the part in between, until no such pair exists.
+    ///None if the tour visits a vertex outside the graph.
+    pub fn two_opt(&self, tour: &Tour<'a, VertexKey>) -> Option<Tour<'a, VertexKey>> {
+        let mut tour = self.indices(tour)?;
+        let size = tour.len();
+        let distance = &self.distance;
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..size.saturating_sub(2) {
+                for j in i + 2..size {
+                    if i == 0 && j == size - 1 {
+                        continue;
+                    }
+                    let (a, b) = (tour[i], tour[i + 1]);
+                    let (c, d) = (tour[j], tour[(j + 1) % size]);
+                    let change = distance[a][c] + distance[b][d] - distance[a][b] - distance[c][d];
+                    if change < -EPSILON {
+                        tour[i + 1..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+        let cost = self.cost(&tour);
+        Some(Tour {
+            vertices: tour.into_iter().map(|x| self.keys[x]).collect(),
+            cost,
+        })
+    }
+
+    ///Repeatedly moves a segment of up to three consecutive vertices elsewhere in the tour, possibly reversed, while that shortens it.
+    ///None if the tour visits a vertex outside the graph.
+    pub fn or_opt(&self, tour: &Tour<'a, VertexKey>) -> Option<Tour<'a, VertexKey>> {
+        let mut tour = self.indices(tour)?;
+        while let Some(better) = self.or_opt_move(&tour) {
+            tour = better;
+        }
+        let cost = self.cost(&tour);
+        Some(Tour {
+            vertices: tour.into_iter().map(|x| self.keys[x]).collect(),
+            cost,
+        })
+    }
+
+    fn or_opt_move(&self, tour: &[usize]) -> Option<Vec<usize>> {
+        let size = tour.len();
+        let distance = &self.distance;
+        for length in 1..=3 {
+            if size < length + 3 {
+                break;
+            }
+            for start in 0..=size - length {
+                let (first, last) = (tour[start], tour[start + length - 1]);
+                let before = tour[(start + size - 1) % size];
+                let after = tour[(start + length) % size];
+                let removed =
+                    distance[before][first] + distance[last][after] - distance[before][after];
+
+                let mut rest: Vec<_> = tour[..start].to_vec();
+                rest.extend_from_slice(&tour[start + length..]);
+                for i in 0..rest.len() {
+                    let (p, q) = (rest[i], rest[(i + 1) % rest.len()]);
+                    if p == before && q == after {
+                        continue;
+                    }
+                    let forward = distance[p][first] + distance[last][q] - distance[p][q];
+                    let backward = distance[p][last] + distance[first][q] - distance[p][q];
+                    let reversed = backward < forward;
+                    if forward.min(backward) - removed < -EPSILON {
+                        let mut segment = tour[start..start + length].to_vec();
+                        if reversed {
+                            segment.reverse();
+                        }
+                        rest.splice(i + 1..i + 1, segment);
+                        return Some(rest);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    ///Christofides' algorithm, joining a minimum spanning tree with a minimum weight perfect matching of its odd vertices, and shortcutting the resulting Eulerian circuit.
+    ///Within 1.5 times the optimum for metric distances.
+    pub fn christofides(&self) -> Option<Tour<'a, VertexKey>> {
+        let size = self.keys.len();
+        if size < 3 {
+            return self.tour((0..size).collect());
+        }
+        let distance = &self.distance;
+
+        let mut parent = vec![0; size];
+        let mut best = distance[0].clone();
+        let mut added = vec![false; size];
+        added[0] = true;
+        let mut edges = Vec::with_capacity(2 * size);
+        for _ in 1..size {
+            let next = (0..size)
+                .filter(|&x| !added[x])
+                .min_by(|&a, &b| best[a].total_cmp(&best[b]))?;
+            if !best[next].is_finite() {
+                return None;
+            }
+            added[next] = true;
+            edges.push((parent[next], next));
+            for x in 0..size {
+                if !added[x] && distance[next][x] < best[x] {
+                    best[x] = distance[next][x];
+                    parent[x] = next;
+                }
+            }
+        }
+
+        let mut degree = vec![0; size];
+        for &(a, b) in &edges {
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+        let odd: Vec<_> = (0..size).filter(|&x| degree[x] % 2 == 1).collect();
+        edges.extend(self.perfect_matching(&odd)?);
+
+        let mut adjacent = vec![Vec::new(); size];
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            adjacent[a].push((i, b));
+            adjacent[b].push((i, a));
+        }
+        let mut used = vec![false; edges.len()];
+        let mut stack = vec![0];
+        let mut visited = vec![false; size];
+        let mut tour = Vec::with_capacity(size);
+        while let Some(&vertex) = stack.last() {
+            match adjacent[vertex].iter().find(|x| !used[x.0]) {
+                Some(&(edge, to)) => {
+                    used[edge] = true;
+                    stack.push(to);
+                }
+                None => {
+                    stack.pop();
+                    if !visited[vertex] {
+                        visited[vertex] = true;
+                        tour.push(vertex);
+                    }
+                }
+            }
+        }
+        self.tour(tour)
+    }
+
+    ///A minimum weight perfect matching of the given vertices, None if the finite distances between them admit none.
+    ///The distances are rounded to 32 bits of precision relative to the longest one, such that the weights are exact integers.
+    fn perfect_matching(&self, vertices: &[usize]) -> Option<Vec<(usize, usize)>> {
+        let longest = vertices
+            .iter()
+            .flat_map(|&a| vertices.iter().map(move |&b| self.distance[a][b]))
+            .filter(|x| x.is_finite())
+            .fold(0.0, |longest, x| x.abs().max(longest));
+        let scale = if longest > 0.0 {
+            PRECISION as f64 / longest
+        } else {
+            0.0
+        };
+        //Minimising the distance is maximising its complement, where every perfect matching outweighs any smaller one.
+        let complement = vertices.len() as i64 * PRECISION + 1;
+
+        let mut blossom = WeightedBlossom::new(vertices.len());
+        for (i, &a) in vertices.iter().enumerate() {
+            for (j, &b) in vertices.iter().enumerate() {
+                let distance = self.distance[a][b];
+                if i != j && distance.is_finite() {
+                    let weight = complement - (distance * scale).round() as i64;
+                    blossom.arcs[i + 1][j + 1].weight = 2 * weight;
+                }
+            }
+        }
+        let matching = blossom.solve()?;
+        Some(
+            matching
+                .into_iter()
+                .map(|(a, b)| (vertices[a], vertices[b]))
+                .collect(),
+        )
+    }
+
+    ///The shortest tour, found by Held and Karp's dynamic programming over subsets of vertices.
+    ///None if there is no tour, or if there are more than HELD_KARP_LIMIT vertices.
+    pub fn held_karp(&self) -> Option<Tour<'a, VertexKey>> {
+        let size = self.keys.len();
+        if size > HELD_KARP_LIMIT {
+            return None;
+        }
+        if size < 3 {
+            return self.tour((0..size).collect());
+        }
+        let distance = &self.distance;
+        let others = size - 1;
+        let full = (1usize << others) - 1;
+
+        let mut cost = vec![vec![f64::INFINITY; others]; full + 1];
+        let mut previous = vec![vec![0; others]; full + 1];
+        for last in 0..others {
+            cost[1 << last][last] = distance[0][last + 1];
+        }
+        for mask in 1..=full {
+            for last in 0..others {
+                if mask & (1 << last) == 0 || !cost[mask][last].is_finite() {
+                    continue;
+                }
+                for next in 0..others {
+                    if mask & (1 << next) != 0 {
+                        continue;
+                    }
+                    let extended = mask | 1 << next;
+                    let total = cost[mask][last] + distance[last + 1][next + 1];
+                    if total < cost[extended][next] {
+                        cost[extended][next] = total;
+                        previous[extended][next] = last;
+                    }
+                }
+            }
+        }
+
+        let last = (0..others).min_by(|&a, &b| {
+            let a = cost[full][a] + distance[a + 1][0];
+            let b = cost[full][b] + distance[b + 1][0];
+            a.total_cmp(&b)
+        })?;
+        if !(cost[full][last] + distance[last + 1][0]).is_finite() {
+            return None;
+        }
+        let mut tour = Vec::with_capacity(size);
+        let (mut mask, mut current) = (full, last);
+        while mask != 0 {
+            tour.push(current + 1);
+            let before = previous[mask][current];
+            mask &= !(1 << current);
+            current = before;
+        }
+        tour.push(0);
+        tour.reverse();
+        self.tour(tour)
+    }
+}
+
+pub trait Travelling<'a, VertexKey, EdgeKey>
+where
+    Self: Sized,
+    VertexKey: Eq + Hash,
+{
+    ///Prepares the travelling salesman heuristics, measuring the distance of each edge with the given function.
+    fn salesman<Function>(&'a self, distance: Function) -> Salesman<'a, VertexKey>
+    where
+        Self: GetEdge<EdgeKey>,
+        Function: Fn(&<Self as GetEdge<EdgeKey>>::Output) -> f64;
+}
+
+impl<'a, VertexKey, EdgeKey, Graph> Travelling<'a, VertexKey, EdgeKey> for Graph
+where
+    VertexKey: 'a + Eq + Hash,
+    EdgeKey: 'a,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Undirected, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn salesman<Function>(&'a self, distance: Function) -> Salesman<'a, VertexKey>
+    where
+        Self: GetEdge<EdgeKey>,
+        Function: Fn(&<Self as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        Salesman::new(self, distance)
+    }
+}
+
+///An edge of the weighted blossom algorithm, keeping the original vertices it joins when it leaves a blossom.
+#[derive(Clone, Copy)]
+struct Arc {
+    from: usize,
+    to: usize,
+    weight: i64,
+}
+
+///Maximum weight matching of a dense graph with positive even weights, found using the primal dual blossom algorithm in O(n³).
+///Vertices are numbered from 1, 0 standing for none, and blossoms are numbered after the vertices.
+///Arcs of weight 0 are absent.
+struct WeightedBlossom {
+    size: usize,
+    count: usize,
+    arcs: Vec<Vec<Arc>>,
+    label: Vec<i64>,
+    mate: Vec<usize>,
+    slack: Vec<usize>,
+    top: Vec<usize>,
+    parent: Vec<usize>,
+    //The sub blossom of a blossom containing a given vertex.
+    origin: Vec<Vec<usize>>,
+    //-1 for unlabeled, 0 for outer and 1 for inner blossoms.
+    side: Vec<i8>,
+    visited: Vec<usize>,
+    time: usize,
+    flower: Vec<Vec<usize>>,
+    queue: VecDeque<usize>,
+}
+
+impl WeightedBlossom {
+    fn new(size: usize) -> Self {
+        let total = 2 * size + 1;
+        Self {
+            size,
+            count: size,
+            arcs: (0..total)
+                .map(|from| {
+                    (0..total)
+                        .map(|to| Arc {
+                            from,
+                            to,
+                            weight: 0,
+                        })
+                        .collect()
+                })
+                .collect(),
+            label: vec![0; total],
+            mate: vec![0; total],
+            slack: vec![0; total],
+            top: (0..total).collect(),
+            parent: vec![0; total],
+            origin: (0..total)
+                .map(|b| (0..=size).map(|x| if x == b { x } else { 0 }).collect())
+                .collect(),
+            side: vec![-1; total],
+            visited: vec![0; total],
+            time: 0,
+            flower: vec![Vec::new(); total],
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn distance(&self, arc: Arc) -> i64 {
+        self.label[arc.from] + self.label[arc.to] - 2 * arc.weight
+    }
+
+    fn update_slack(&mut self, vertex: usize, blossom: usize) {
+        let slack = self.slack[blossom];
+        if slack == 0
+            || self.distance(self.arcs[vertex][blossom]) < self.distance(self.arcs[slack][blossom])
+        {
+            self.slack[blossom] = vertex;
+        }
+    }
+
+    fn set_slack(&mut self, blossom: usize) {
+        self.slack[blossom] = 0;
+        for vertex in 1..=self.size {
+            if self.arcs[vertex][blossom].weight > 0
+                && self.top[vertex] != blossom
+                && self.side[self.top[vertex]] == 0
+            {
+                self.update_slack(vertex, blossom);
+            }
+        }
+    }
+
+    ///Queues every vertex inside the given blossom.
+    fn push(&mut self, blossom: usize) {
+        let mut stack = vec![blossom];
+        while let Some(current) = stack.pop() {
+            if current <= self.size {
+                self.queue.push_back(current);
+            } else {
+                stack.extend_from_slice(&self.flower[current]);
+            }
+        }
+    }
+
+    fn set_top(&mut self, blossom: usize, top: usize) {
+        let mut stack = vec![blossom];
+        while let Some(current) = stack.pop() {
+            self.top[current] = top;
+            if current > self.size {
+                stack.extend_from_slice(&self.flower[current]);
+            }
+        }
+    }
+
+    ///The even position of the given sub blossom, reversing the blossom if its position is odd.
+    fn position(&mut self, blossom: usize, sub: usize) -> usize {
+        let flower = &mut self.flower[blossom];
+        let position = flower.iter().position(|&x| x == sub).unwrap();
+        if position % 2 == 1 {
+            flower[1..].reverse();
+            flower.len() - position
+        } else {
+            position
+        }
+    }
+
+    fn set_mate(&mut self, blossom: usize, to: usize) {
+        let arc = self.arcs[blossom][to];
+        self.mate[blossom] = arc.to;
+        if blossom > self.size {
+            let sub = self.origin[blossom][arc.from];
+            let position = self.position(blossom, sub);
+            for i in 0..position {
+                let (a, b) = (self.flower[blossom][i], self.flower[blossom][i ^ 1]);
+                self.set_mate(a, b);
+            }
+            self.set_mate(sub, to);
+            self.flower[blossom].rotate_left(position);
+        }
+    }
+
+    fn augment(&mut self, mut from: usize, mut to: usize) {
+        loop {
+            let next = self.top[self.mate[from]];
+            self.set_mate(from, to);
+            if next == 0 {
+                return;
+            }
+            let parent = self.top[self.parent[next]];
+            self.set_mate(next, parent);
+            from = parent;
+            to = next;
+        }
+    }
+
+    fn lowest_common_ancestor(&mut self, mut a: usize, mut b: usize) -> usize {
+        self.time += 1;
+        while a != 0 || b != 0 {
+            if a != 0 {
+                if self.visited[a] == self.time {
+                    return a;
+                }
+                self.visited[a] = self.time;
+                a = self.top[self.mate[a]];
+                if a != 0 {
+                    a = self.top[self.parent[a]];
+                }
+            }
+            std::mem::swap(&mut a, &mut b);
+        }
+        0
+    }
+
+    fn add_blossom(&mut self, from: usize, ancestor: usize, to: usize) {
+        let mut blossom = self.size + 1;
+        while blossom <= self.count && self.top[blossom] != 0 {
+            blossom += 1;
+        }
+        if blossom > self.count {
+            self.count += 1;
+        }
+        self.label[blossom] = 0;
+        self.side[blossom] = 0;
+        self.mate[blossom] = self.mate[ancestor];
+        let mut flower = vec![ancestor];
+        for &(start, reverse) in &[(from, true), (to, false)] {
+            let mut vertex = start;
+            while vertex != ancestor {
+                let mate = self.top[self.mate[vertex]];
+                flower.push(vertex);
+                flower.push(mate);
+                self.push(mate);
+                vertex = self.top[self.parent[mate]];
+            }
+            if reverse {
+                flower[1..].reverse();
+            }
+        }
+        self.flower[blossom] = flower;
+        self.set_top(blossom, blossom);
+
+        for x in 1..=self.count {
+            self.arcs[blossom][x].weight = 0;
+            self.arcs[x][blossom].weight = 0;
+        }
+        for x in 1..=self.size {
+            self.origin[blossom][x] = 0;
+        }
+        for i in 0..self.flower[blossom].len() {
+            let sub = self.flower[blossom][i];
+            for x in 1..=self.count {
+                if self.arcs[blossom][x].weight == 0
+                    || self.distance(self.arcs[sub][x]) < self.distance(self.arcs[blossom][x])
+                {
+                    self.arcs[blossom][x] = self.arcs[sub][x];
+                    self.arcs[x][blossom] = self.arcs[x][sub];
+                }
+            }
+            for x in 1..=self.size {
+                if self.origin[sub][x] != 0 {
+                    self.origin[blossom][x] = sub;
+                }
+            }
+        }
+        self.set_slack(blossom);
+    }
+
+    ///Dissolves an inner blossom whose label dropped to 0, labelling the sub blossoms on the even path to its base.
+    fn expand_blossom(&mut self, blossom: usize) {
+        for i in 0..self.flower[blossom].len() {
+            let sub = self.flower[blossom][i];
+            self.set_top(sub, sub);
+        }
+        let entry = self.arcs[blossom][self.parent[blossom]].from;
+        let sub = self.origin[blossom][entry];
+        let position = self.position(blossom, sub);
+        for i in (0..position).step_by(2) {
+            let (a, b) = (self.flower[blossom][i], self.flower[blossom][i + 1]);
+            self.parent[a] = self.arcs[b][a].from;
+            self.side[a] = 1;
+            self.side[b] = 0;
+            self.slack[a] = 0;
+            self.set_slack(b);
+            self.push(b);
+        }
+        self.side[sub] = 1;
+        self.parent[sub] = self.parent[blossom];
+        for i in position + 1..self.flower[blossom].len() {
+            let rest = self.flower[blossom][i];
+            self.side[rest] = -1;
+            self.set_slack(rest);
+        }
+        self.top[blossom] = 0;
+    }
+
+    ///Handles a tight arc, returning whether it completed an augmenting path.
+    fn tight(&mut self, arc: Arc) -> bool {
+        let (from, to) = (self.top[arc.from], self.top[arc.to]);
+        if self.side[to] == -1 {
+            self.parent[to] = arc.from;
+            self.side[to] = 1;
+            let mate = self.top[self.mate[to]];
+            self.slack[to] = 0;
+            self.slack[mate] = 0;
+            self.side[mate] = 0;
+            self.push(mate);
+        } else if self.side[to] == 0 {
+            match self.lowest_common_ancestor(from, to) {
+                0 => {
+                    self.augment(from, to);
+                    self.augment(to, from);
+                    return true;
+                }
+                ancestor => self.add_blossom(from, ancestor, to),
+            }
+        }
+        false
+    }
+
+    ///Grows alternating trees from every free blossom, adjusting the labels until an augmenting path is found.
+    fn augmenting_path(&mut self) -> bool {
+        for x in 1..=self.count {
+            self.side[x] = -1;
+            self.slack[x] = 0;
+        }
+        self.queue.clear();
+        for x in 1..=self.count {
+            if self.top[x] == x && self.mate[x] == 0 {
+                self.parent[x] = 0;
+                self.side[x] = 0;
+                self.push(x);
+            }
+        }
+        if self.queue.is_empty() {
+            return false;
+        }
+        loop {
+            while let Some(vertex) = self.queue.pop_front() {
+                if self.side[self.top[vertex]] == 1 {
+                    continue;
+                }
+                for to in 1..=self.size {
+                    let arc = self.arcs[vertex][to];
+                    if arc.weight > 0 && self.top[vertex] != self.top[to] {
+                        if self.distance(arc) == 0 {
+                            if self.tight(arc) {
+                                return true;
+                            }
+                        } else {
+                            self.update_slack(vertex, self.top[to]);
+                        }
+                    }
+                }
+            }
+
+            let mut delta = i64::MAX;
+            for b in self.size + 1..=self.count {
+                if self.top[b] == b && self.side[b] == 1 {
+                    delta = delta.min(self.label[b] / 2);
+                }
+            }
+            for x in 1..=self.count {
+                let slack = self.slack[x];
+                if self.top[x] == x && slack != 0 {
+                    let distance = self.distance(self.arcs[slack][x]);
+                    match self.side[x] {
+                        -1 => delta = delta.min(distance),
+                        0 => delta = delta.min(distance / 2),
+                        _ => (),
+                    }
+                }
+            }
+            for vertex in 1..=self.size {
+                match self.side[self.top[vertex]] {
+                    0 if self.label[vertex] <= delta => return false,
+                    0 => self.label[vertex] -= delta,
+                    1 => self.label[vertex] += delta,
+                    _ => (),
+                }
+            }
+            for b in self.size + 1..=self.count {
+                if self.top[b] == b {
+                    match self.side[b] {
+                        0 => self.label[b] += 2 * delta,
+                        1 => self.label[b] -= 2 * delta,
+                        _ => (),
+                    }
+                }
+            }
+
+            self.queue.clear();
+            for x in 1..=self.count {
+                let slack = self.slack[x];
+                if self.top[x] == x
+                    && slack != 0
+                    && self.top[slack] != x
+                    && self.distance(self.arcs[slack][x]) == 0
+                    && self.tight(self.arcs[slack][x])
+                {
+                    return true;
+                }
+            }
+            for b in self.size + 1..=self.count {
+                if self.top[b] == b && self.side[b] == 1 && self.label[b] == 0 {
+                    self.expand_blossom(b);
+                }
+            }
+        }
+    }
+
+    ///The pairs of a perfect matching of maximum weight, numbered from 0. None if there is no perfect matching.
+    fn solve(mut self) -> Option<Vec<(usize, usize)>> {
+        let heaviest = self.arcs.iter().flatten().map(|x| x.weight).max();
+        for vertex in 1..=self.size {
+            self.label[vertex] = heaviest.unwrap_or(0);
+        }
+        while self.augmenting_path() {}
+        let mut matching = Vec::new();
+        for vertex in 1..=self.size {
+            match self.mate[vertex] {
+                0 => return None,
+                mate if vertex < mate => matching.push((vertex - 1, mate - 1)),
+                _ => (),
+            }
+        }
+        Some(matching)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::AddEdge;
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    ///A complete graph over points in the plane, weighted by their euclidean distance.
+    fn points(points: &[(f64, f64)]) -> Oriented<Simple<usize, (), usize, f64>, Undirected> {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..points.len() {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        let mut key = 0;
+        for (i, a) in points.iter().enumerate() {
+            for (j, b) in points.iter().enumerate().skip(i + 1) {
+                let distance = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+                graph.add_edge(&i, &j, (key, distance)).unwrap();
+                key += 1;
+            }
+        }
+        graph
+    }
+
+    fn square() -> Oriented<Simple<usize, (), usize, f64>, Undirected> {
+        points(&[
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+        ])
+    }
+
+    fn visits_all(tour: &Tour<usize>, size: usize) -> bool {
+        let mut vertices: Vec<_> = tour.vertices.iter().map(|&&x| x).collect();
+        vertices.sort();
+        vertices == (0..size).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn held_karp() {
+        let graph = square();
+        let tour = graph.salesman(|x| *x).held_karp().unwrap();
+        assert!(visits_all(&tour, 6));
+        assert!((tour.cost - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn heuristics() {
+        let graph = square();
+        let salesman = graph.salesman(|x| *x);
+
+        let nearest = salesman.nearest_neighbour(&0).unwrap();
+        assert_eq!(nearest.vertices[0], &0);
+        assert!(visits_all(&nearest, 6));
+
+        let improved = salesman
+            .or_opt(&salesman.two_opt(&nearest).unwrap())
+            .unwrap();
+        assert!(visits_all(&improved, 6));
+        assert!(improved.cost <= nearest.cost + 1e-9);
+        assert!((improved.cost - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn two_opt_uncrosses() {
+        let graph = points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let salesman = graph.salesman(|x| *x);
+        let crossed = Tour {
+            vertices: vec![&0, &2, &1, &3],
+            cost: 0.0,
+        };
+        assert!((salesman.two_opt(&crossed).unwrap().cost - 4.0).abs() < 1e-9);
+
+        let outside = Tour {
+            vertices: vec![&0, &1, &2, &4],
+            cost: 0.0,
+        };
+        assert_eq!(salesman.two_opt(&outside), None);
+        assert_eq!(salesman.or_opt(&outside), None);
+    }
+
+    #[test]
+    fn christofides() {
+        let graph = square();
+        let salesman = graph.salesman(|x| *x);
+        let tour = salesman.christofides().unwrap();
+        assert!(visits_all(&tour, 6));
+        assert!(tour.cost <= 1.5 * 6.0 + 1e-9);
+    }
+
+    ///The cost of the cheapest perfect matching, trying every pairing of the first vertex.
+    fn cheapest(salesman: &Salesman<usize>, vertices: &[usize]) -> f64 {
+        match vertices.split_first() {
+            None => 0.0,
+            Some((&first, rest)) => (0..rest.len())
+                .map(|i| {
+                    let mut others = rest.to_vec();
+                    let second = others.remove(i);
+                    salesman.distance[first][second] + cheapest(salesman, &others)
+                })
+                .fold(f64::INFINITY, f64::min),
+        }
+    }
+
+    #[test]
+    fn perfect_matching() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let coordinates: Vec<_> = (0..10).map(|_| (rng.gen(), rng.gen())).collect();
+            let graph = points(&coordinates);
+            let salesman = graph.salesman(|x| *x);
+            let vertices: Vec<_> = (0..10).collect();
+
+            let matching = salesman.perfect_matching(&vertices).unwrap();
+            let mut matched: Vec<_> = matching.iter().flat_map(|&(a, b)| vec![a, b]).collect();
+            matched.sort();
+            assert_eq!(matched, vertices);
+            let cost: f64 = matching.iter().map(|&(a, b)| salesman.distance[a][b]).sum();
+            assert!((cost - cheapest(&salesman, &vertices)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn held_karp_limit() {
+        let coordinates: Vec<_> = (0..=HELD_KARP_LIMIT).map(|x| (x as f64, 0.0)).collect();
+        let graph = points(&coordinates);
+        let salesman = graph.salesman(|x| *x);
+        assert_eq!(salesman.held_karp(), None);
+        assert!(salesman.christofides().is_some());
+    }
+
+    #[test]
+    fn infinite_weights() {
+        //A path of weight minus infinity, closed by an edge of weight infinity, sums to NaN.
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        let edges = [
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, f64::NEG_INFINITY),
+            (3, 0, f64::INFINITY),
+            (0, 2, 1.0),
+            (1, 3, 1.0),
+        ];
+        for (key, &(from, to, weight)) in edges.iter().enumerate() {
+            graph.add_edge(&from, &to, (key, weight)).unwrap();
+        }
+        assert_eq!(graph.salesman(|x| *x).held_karp(), None);
+    }
+
+    #[test]
+    fn incomplete() {
+        let mut graph = Simple::default().orient(Undirected);
+        for i in 0..4 {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        graph.add_edge(&0, &1, (0, 1.0)).unwrap();
+        graph.add_edge(&1, &2, (1, 1.0)).unwrap();
+        graph.add_edge(&2, &3, (2, 1.0)).unwrap();
+
+        let salesman = graph.salesman(|x| *x);
+        assert_eq!(salesman.held_karp(), None);
+        assert_eq!(salesman.nearest_neighbour(&1), None);
+        assert_eq!(salesman.christofides(), None);
+    }
+}
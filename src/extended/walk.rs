@@ -0,0 +1,299 @@
+use crate::dev::{orientation, GetEdge, Neighbours, Vertices};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+type Weight<'a, EdgeKey> = Box<dyn 'a + Fn(&EdgeKey) -> f64>;
+
+///Random walks, moving to a neighbour chosen in proportion to the weight of the connecting edge. Setting a bias gives the second order walks of node2vec.
+///Neighbours are visited in order of their keys, and parallel edges in order of theirs, so the walks only depend on the state of the given random number generator.
+pub struct RandomWalk<'a, Graph, VertexKey, EdgeKey, Orientation> {
+    graph: &'a Graph,
+    length: usize,
+    weight: Option<Weight<'a, EdgeKey>>,
+    bias: Option<(f64, f64)>,
+    phantom: PhantomData<(VertexKey, Orientation)>,
+}
+
+impl<'a, Graph, VertexKey, EdgeKey, Orientation>
+    RandomWalk<'a, Graph, VertexKey, EdgeKey, Orientation>
+where
+    Orientation: orientation::Orientation,
+    VertexKey: 'a + Eq + Hash + Ord,
+    EdgeKey: 'a + Ord,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+{
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            length: 80,
+            weight: None,
+            bias: None,
+            phantom: PhantomData,
+        }
+    }
+
+    ///The number of vertices in every walk, including the start. Defaults to 80.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    ///Uses the weights of the edges as transition probabilities, rather than choosing uniformly between the neighbours.
+    pub fn weighted<Function>(mut self, function: Function) -> Self
+    where
+        Graph: GetEdge<EdgeKey>,
+        Function: 'a + Fn(&<Graph as GetEdge<EdgeKey>>::Output) -> f64,
+    {
+        let graph = self.graph;
+        self.weight = Some(Box::new(move |edge| {
+            graph.get_edge(edge).map_or(0.0, &function)
+        }));
+        self
+    }
+
+    ///The return parameter p and in-out parameter q of node2vec. Stepping back to the previous vertex is weighted by 1/p,
+    ///to a neighbour of the previous vertex by 1, and to any other vertex by 1/q.
+    pub fn bias(mut self, p: f64, q: f64) -> Self {
+        self.bias = Some((p, q));
+        self
+    }
+
+    ///Every neighbour once, along with the summed weight of the edges leading to it, being 1 without weights.
+    fn neighbours(&self, vertex: &'a VertexKey) -> Vec<(&'a VertexKey, f64)> {
+        let mut edges: Vec<_> = self
+            .graph
+            .neighbours(vertex)
+            .into_iter()
+            .flatten()
+            .collect();
+        edges.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+
+        let mut neighbours: Vec<(&'a VertexKey, f64)> = Vec::new();
+        for (edge, to) in edges {
+            let weight = self.weight.as_ref().map(|x| x(edge));
+            match (neighbours.last_mut(), weight) {
+                (Some(last), Some(weight)) if last.0 == to => last.1 += weight,
+                (Some(last), None) if last.0 == to => {}
+                _ => neighbours.push((to, weight.unwrap_or(1.0))),
+            }
+        }
+        neighbours
+    }
+
+    fn step<R>(
+        &self,
+        previous: Option<&'a VertexKey>,
+        current: &'a VertexKey,
+        rng: &mut R,
+    ) -> Option<&'a VertexKey>
+    where
+        R: Rng,
+    {
+        let candidates = self.neighbours(current);
+        let around: Option<HashSet<_>> = match (self.bias, previous) {
+            (Some(_), Some(previous)) => {
+                Some(self.neighbours(previous).into_iter().map(|x| x.0).collect())
+            }
+            _ => None,
+        };
+        let weights = candidates
+            .iter()
+            .map(|&(to, weight)| match (self.bias, previous, &around) {
+                (Some((p, _)), Some(previous), _) if to == previous => weight / p,
+                (Some(_), _, Some(around)) if around.contains(to) => weight,
+                (Some((_, q)), Some(_), _) => weight / q,
+                _ => weight,
+            });
+        let index = WeightedIndex::new(weights).ok()?.sample(rng);
+        Some(candidates[index].0)
+    }
+
+    ///A walk from the given vertex, ending early at vertices without neighbours to move to.
+    pub fn walk<R>(&self, start: &'a VertexKey, rng: &mut R) -> Vec<&'a VertexKey>
+    where
+        R: Rng,
+    {
+        let mut walk = vec![start];
+        let mut previous = None;
+        while walk.len() < self.length {
+            let current = walk[walk.len() - 1];
+            match self.step(previous, current, rng) {
+                Some(next) => walk.push(next),
+                None => break,
+            }
+            previous = Some(current);
+        }
+        walk.truncate(self.length);
+        walk
+    }
+
+    ///The given number of walks from every vertex, going through the vertices in a new random order each round.
+    pub fn walks<R>(&self, count: usize, rng: &mut R) -> Vec<Vec<&'a VertexKey>>
+    where
+        R: Rng,
+    {
+        let mut keys: Vec<_> = self.graph.vertices().into_iter().collect();
+        let mut walks = Vec::with_capacity(count * keys.len());
+        keys.sort();
+        for _ in 0..count {
+            keys.shuffle(rng);
+            for &key in &keys {
+                walks.push(self.walk(key, rng));
+            }
+        }
+        walks
+    }
+}
+
+pub trait Walk<'a, Orientation, VertexKey, EdgeKey>
+where
+    Self: Sized,
+{
+    fn random_walk(&'a self) -> RandomWalk<'a, Self, VertexKey, EdgeKey, Orientation>;
+}
+
+impl<'a, Orientation, Graph, VertexKey, EdgeKey> Walk<'a, Orientation, VertexKey, EdgeKey> for Graph
+where
+    Orientation: orientation::Orientation,
+    VertexKey: 'a + Eq + Hash + Ord,
+    EdgeKey: 'a + Ord,
+    Graph:
+        Vertices<'a, Item = VertexKey> + Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+{
+    fn random_walk(&'a self) -> RandomWalk<'a, Self, VertexKey, EdgeKey, Orientation> {
+        RandomWalk::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::{AddEdge, Directed, Undirected};
+    use crate::dev::simple::Simple;
+    use crate::dev::AddVertex;
+    use crate::wrapper::oriented::{Orient, Oriented};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn graph<O: orientation::Orientation>(
+        orientation: O,
+        size: usize,
+        edges: &[(usize, usize, f64)],
+    ) -> Oriented<Simple<usize, (), usize, f64>, O>
+    where
+        Simple<usize, (), usize, f64>: AddEdge<O, usize, (usize, f64), EdgeKey = usize>,
+    {
+        let mut graph = Simple::default().orient(orientation);
+        for i in 0..size {
+            graph.add_vertex((i, ())).unwrap();
+        }
+        for (i, (from, to, weight)) in edges.iter().enumerate() {
+            graph.add_edge(from, to, (i, *weight)).unwrap();
+        }
+        graph
+    }
+
+    fn adjacent<O: orientation::Orientation>(
+        graph: &Oriented<Simple<usize, (), usize, f64>, O>,
+        walk: &[&usize],
+    ) -> bool {
+        walk.windows(2).all(|x| {
+            graph.edges.values().any(|edge| {
+                (edge.from == *x[0] && edge.to == *x[1]) || (edge.from == *x[1] && edge.to == *x[0])
+            })
+        })
+    }
+
+    #[test]
+    fn uniform() {
+        let cycle = graph(
+            Undirected,
+            4,
+            &[(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0), (3, 0, 1.0)],
+        );
+        let walker = cycle.random_walk().length(20);
+        let walk = walker.walk(&0, &mut StdRng::seed_from_u64(3));
+        assert_eq!(walk.len(), 20);
+        assert_eq!(walk[0], &0);
+        assert!(adjacent(&cycle, &walk));
+        assert_eq!(walk, walker.walk(&0, &mut StdRng::seed_from_u64(3)));
+
+        let walks = walker.walks(3, &mut StdRng::seed_from_u64(5));
+        assert_eq!(walks.len(), 12);
+        assert_eq!(walks, walker.walks(3, &mut StdRng::seed_from_u64(5)));
+    }
+
+    #[test]
+    fn dead_end() {
+        let path = graph(Directed, 3, &[(0, 1, 1.0), (1, 2, 1.0)]);
+        let walk = path
+            .random_walk()
+            .length(10)
+            .walk(&0, &mut StdRng::seed_from_u64(0));
+        assert_eq!(walk, vec![&0, &1, &2]);
+    }
+
+    #[test]
+    fn parallel() {
+        let star = graph(Directed, 3, &[(0, 1, 0.5), (0, 2, 1.0), (0, 1, 0.25)]);
+        assert_eq!(
+            star.random_walk().neighbours(&0),
+            vec![(&1, 1.0), (&2, 1.0)]
+        );
+        let weighted = star.random_walk().weighted(|x| *x);
+        assert_eq!(weighted.neighbours(&0), vec![(&1, 0.75), (&2, 1.0)]);
+
+        //Only the second of the parallel edges can be taken, which must not keep the walk from moving.
+        let parallel = graph(Directed, 2, &[(0, 1, 0.0), (0, 1, 1.0), (1, 0, 1.0)]);
+        let walk = parallel
+            .random_walk()
+            .length(4)
+            .weighted(|x| *x)
+            .walk(&0, &mut StdRng::seed_from_u64(4));
+        assert_eq!(walk, vec![&0, &1, &0, &1]);
+    }
+
+    #[test]
+    fn weighted() {
+        let star = graph(
+            Directed,
+            3,
+            &[(0, 1, 0.0), (0, 2, 1.0), (1, 0, 1.0), (2, 0, 1.0)],
+        );
+        let walk = star
+            .random_walk()
+            .length(30)
+            .weighted(|x| *x)
+            .walk(&0, &mut StdRng::seed_from_u64(1));
+        assert!(walk.iter().all(|&&x| x != 1));
+    }
+
+    #[test]
+    fn node2vec() {
+        //With a tiny return parameter the walk keeps stepping back, and with a tiny in-out parameter it keeps moving away.
+        let path = graph(
+            Undirected,
+            5,
+            &[(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0), (3, 4, 1.0)],
+        );
+        let mut rng = StdRng::seed_from_u64(2);
+        let back = path
+            .random_walk()
+            .length(6)
+            .bias(1e-9, 1.0)
+            .walk(&1, &mut rng);
+        assert!(back.windows(3).all(|x| x[0] == x[2]));
+
+        let out = path
+            .random_walk()
+            .length(5)
+            .bias(1.0, 1e-9)
+            .walk(&0, &mut rng);
+        assert_eq!(out, vec![&0, &1, &2, &3, &4]);
+    }
+}
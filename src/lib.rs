@@ -4,5 +4,6 @@ pub mod extended;
 pub mod wrapper;
 
 pub mod graph {
+    pub use crate::dev::indexed::Indexed;
     pub use crate::dev::simple::Simple;
 }
@@ -58,22 +58,23 @@ impl<Graph, VertexKey, EdgeKey, Value, Orientation> EdgeTrait<Orientation, Verte
     for Edge<Graph, EdgeKey>
 where
     Orientation: orientation::Orientation,
-    Graph: EdgeTrait<Orientation, VertexKey, (EdgeKey, Value)>,
+    Graph: EdgeTrait<Orientation, VertexKey, (EdgeKey, Value)> + GetEdge<EdgeKey>,
     Standard: Distribution<EdgeKey>,
 {
     type EdgeKey = <Graph as EdgeTrait<Orientation, VertexKey, (EdgeKey, Value)>>::EdgeKey;
 
+    ///Draws random keys until one is unused, such that any other error, like a missing vertex, is returned as is.
     fn add_edge(
         &mut self,
         from: &VertexKey,
         to: &VertexKey,
         value: Value,
     ) -> Result<Self::EdgeKey, Value> {
-        let mut output = self.graph.add_edge(from, to, (random(), value));
-        while let Err((_, value)) = output {
-            output = self.graph.add_edge(from, to, (random(), value));
+        let mut key = random();
+        while self.graph.get_edge(&key).is_some() {
+            key = random();
         }
-        output.map_err(|x| x.1)
+        self.graph.add_edge(from, to, (key, value)).map_err(|x| x.1)
     }
 }
 
@@ -249,3 +250,25 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::orientation::{AddEdge, Directed};
+    use crate::dev::simple::Simple;
+    use crate::wrapper::oriented::Orient;
+
+    #[test]
+    fn missing_vertex() {
+        let mut graph: Edge<Simple<usize, (), usize, ()>> = Simple::default().into();
+        graph.add_vertex((0, ())).unwrap();
+        graph.add_vertex((1, ())).unwrap();
+        let mut graph = graph.orient(Directed);
+
+        let first = graph.add_edge(&0, &1, ()).unwrap();
+        let second = graph.add_edge(&0, &1, ()).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(graph.add_edge(&0, &2, ()), Err(()));
+        assert_eq!(graph.edges().count(), 2);
+    }
+}
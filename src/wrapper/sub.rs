@@ -12,18 +12,34 @@ pub struct SubGraph<'a, Graph, Graph2> {
     sub: Graph2,
 }
 
+impl<'a, Graph, Graph2> SubGraph<'a, Graph, Graph2> {
+    ///Creates an empty view into the given parent graph.
+    pub fn new(parent: &'a Graph) -> Self
+    where
+        Graph2: Default,
+    {
+        Self {
+            parent,
+            sub: Default::default(),
+        }
+    }
+}
+
 impl<'a, Graph, Graph2, VertexKey> AddVertex<&'a VertexKey> for SubGraph<'a, Graph, Graph2>
 where
+    VertexKey: Clone,
     Graph: GetVertex<VertexKey>,
-    Graph2: AddVertex<(&'a VertexKey, ())>,
+    Graph2: AddVertex<(VertexKey, ())>,
 {
-    type Key = <Graph2 as AddVertex<(&'a VertexKey, ())>>::Key;
+    type Key = <Graph2 as AddVertex<(VertexKey, ())>>::Key;
 
     fn add_vertex(&mut self, vertex: &'a VertexKey) -> Result<Self::Key, &'a VertexKey> {
         if self.parent.get_vertex(&vertex).is_none() {
             Err(vertex)
         } else {
-            self.sub.add_vertex((vertex, ())).map_err(|x| x.0)
+            self.sub
+                .add_vertex((vertex.clone(), ()))
+                .map_err(|_| vertex)
         }
     }
 }
@@ -138,6 +154,143 @@ where
     }
 }
 
+///A view into a parent graph, which unlike `SubGraph` only follows the edges added to it,
+///rather than every edge between the vertices added to it.
+pub struct PartialSubGraph<'a, Graph, Graph2> {
+    graph: SubGraph<'a, Graph, Graph2>,
+}
+
+impl<'a, Graph, Graph2> PartialSubGraph<'a, Graph, Graph2> {
+    ///Creates an empty view into the given parent graph.
+    pub fn new(parent: &'a Graph) -> Self
+    where
+        Graph2: Default,
+    {
+        Self {
+            graph: SubGraph::new(parent),
+        }
+    }
+}
+
+impl<'a, Graph, Graph2, VertexKey> AddVertex<&'a VertexKey> for PartialSubGraph<'a, Graph, Graph2>
+where
+    VertexKey: Clone,
+    Graph: GetVertex<VertexKey>,
+    Graph2: AddVertex<(VertexKey, ())>,
+{
+    type Key = <Graph2 as AddVertex<(VertexKey, ())>>::Key;
+
+    fn add_vertex(&mut self, vertex: &'a VertexKey) -> Result<Self::Key, &'a VertexKey> {
+        self.graph.add_vertex(vertex)
+    }
+}
+
+impl<'a, Graph, Graph2, Orientation, VertexKey, EdgeKey> AddEdge<Orientation, VertexKey, EdgeKey>
+    for PartialSubGraph<'a, Graph, Graph2>
+where
+    Orientation: orientation::Orientation,
+    Graph: GetEdge<EdgeKey>,
+    Graph2: AddEdge<Orientation, VertexKey, (EdgeKey, ())>,
+{
+    type EdgeKey = <Graph2 as AddEdge<Orientation, VertexKey, (EdgeKey, ())>>::EdgeKey;
+
+    fn add_edge(
+        &mut self,
+        from: &VertexKey,
+        to: &VertexKey,
+        value: EdgeKey,
+    ) -> Result<Self::EdgeKey, EdgeKey> {
+        AddEdge::<Orientation, _, _>::add_edge(&mut self.graph, from, to, value)
+    }
+}
+
+impl<'a, Graph, Graph2, VertexKey> GetVertex<VertexKey> for PartialSubGraph<'a, Graph, Graph2>
+where
+    Graph: GetVertex<VertexKey>,
+    Graph2: GetVertex<VertexKey>,
+{
+    type Output = <Graph as GetVertex<VertexKey>>::Output;
+
+    fn get_vertex(&self, key: &VertexKey) -> Option<&Self::Output> {
+        self.graph.get_vertex(key)
+    }
+}
+
+impl<'a, Graph, Graph2, EdgeKey> GetEdge<EdgeKey> for PartialSubGraph<'a, Graph, Graph2>
+where
+    Graph: GetEdge<EdgeKey>,
+    Graph2: GetEdge<EdgeKey>,
+{
+    type Output = <Graph as GetEdge<EdgeKey>>::Output;
+
+    fn get_edge(&self, key: &EdgeKey) -> Option<&Self::Output> {
+        self.graph.get_edge(key)
+    }
+}
+
+impl<'a, Graph, Graph2, EdgeKey> GetEdgeTo<'a, EdgeKey> for PartialSubGraph<'a, Graph, Graph2>
+where
+    Graph: GetEdgeTo<'a, EdgeKey>,
+    Graph2: GetEdgeTo<'a, EdgeKey>,
+{
+    type Output = <Graph as GetEdgeTo<'a, EdgeKey>>::Output;
+
+    fn get_edge_to(&'a self, key: &EdgeKey) -> Option<Self::Output> {
+        self.graph.get_edge_to(key)
+    }
+}
+
+impl<'a, Graph, Graph2, VertexKey, EdgeKey, Orientation> Neighbours<'a, Orientation, VertexKey>
+    for PartialSubGraph<'a, Graph, Graph2>
+where
+    VertexKey: 'a,
+    EdgeKey: 'a,
+    Orientation: orientation::Orientation,
+    Graph: Neighbours<'a, Orientation, VertexKey, Edge = &'a EdgeKey>,
+    Graph2: Neighbours<'a, Orientation, VertexKey> + GetEdge<EdgeKey>,
+{
+    type Edge = &'a EdgeKey;
+    type IntoIter =
+        Vec<<<Graph as Neighbours<'a, Orientation, VertexKey>>::IntoIter as IntoIterator>::Item>;
+
+    fn neighbours(&'a self, vertex: &VertexKey) -> Option<Self::IntoIter> {
+        let sub = &self.graph.sub;
+        sub.neighbours(vertex)?;
+        let output = self
+            .graph
+            .parent
+            .neighbours(vertex)?
+            .into_iter()
+            .filter(|(edge, x)| sub.neighbours(x).is_some() && sub.get_edge(edge).is_some())
+            .collect();
+        Some(output)
+    }
+}
+
+impl<'a, Graph, Graph2> Vertices<'a> for PartialSubGraph<'a, Graph, Graph2>
+where
+    Graph2: Vertices<'a>,
+{
+    type Item = <Graph2 as Vertices<'a>>::Item;
+    type Output = <Graph2 as Vertices<'a>>::Output;
+
+    fn vertices(&'a self) -> Self::Output {
+        self.graph.vertices()
+    }
+}
+
+impl<'a, Graph, Graph2> Edges<'a> for PartialSubGraph<'a, Graph, Graph2>
+where
+    Graph2: Edges<'a>,
+{
+    type Item = <Graph2 as Edges<'a>>::Item;
+    type Output = <Graph2 as Edges<'a>>::Output;
+
+    fn edges(&'a self) -> Self::Output {
+        self.graph.edges()
+    }
+}
+
 pub fn intersection<T>(mut x: HashSet<T>, y: HashSet<T>) -> HashSet<T>
 where
     T: Eq + Hash,